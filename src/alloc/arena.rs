@@ -0,0 +1,164 @@
+//! A per-frame scratch arena, for memory that's only needed until the frame is drawn.
+//!
+//! The arena is a single block carved out of the bump heap once, which [with_frame_arena] hands
+//! out through a bump pointer. `make_app!` rewinds that pointer after every `__draw`, so the
+//! block gets reused every frame instead of growing the heap.
+
+use core::cell::Cell;
+
+use super::alloc;
+
+/// The size of the frame arena, carved out of the bump heap on its first use
+pub const FRAME_ARENA_SIZE: usize = 2usize.pow(16);
+
+/// The alignment of the arena's memory, and so the maximum alignment of the values in it
+const FRAME_ARENA_ALIGN: usize = 16;
+
+/// The byte freed memory is filled with in debug builds, to make use-after-reset bugs obvious
+pub const POISON: u8 = 0xA5;
+
+/// Scratch memory for short-lived buffers, like vertex lists or formatted strings.
+///
+/// Everything is freed at once by [FrameArena::reset], which happens after every frame for the
+/// global arena (see [with_frame_arena]).
+pub struct FrameArena {
+    base: Cell<*mut u8>,
+    used: Cell<usize>
+}
+
+impl FrameArena {
+    pub const fn new() -> Self {
+        Self {
+            base: Cell::new(core::ptr::null_mut()),
+            used: Cell::new(0)
+        }
+    }
+
+    fn base(&self) -> *mut u8 {
+        if self.base.get().is_null() {
+            let layout = core::alloc::Layout::from_size_align(FRAME_ARENA_SIZE, FRAME_ARENA_ALIGN);
+            self.base.set(unsafe { alloc(layout.unwrap()) });
+        }
+
+        self.base.get()
+    }
+
+    /// Allocate `len` copies of `value`. Returns [None] if the arena is full
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice<T: Copy>(&self, len: usize, value: T) -> Option<&mut [T]> {
+        if align_of::<T>() > FRAME_ARENA_ALIGN {
+            return None;
+        }
+
+        let start = self.used.get().next_multiple_of(align_of::<T>());
+        let end = start.checked_add(size_of::<T>().checked_mul(len)?)?;
+        if end > FRAME_ARENA_SIZE {
+            return None;
+        }
+
+        self.used.set(end);
+
+        let ptr = unsafe { self.base().add(start) } as *mut T;
+        for index in 0..len {
+            unsafe { ptr.add(index).write(value) };
+        }
+
+        Some(unsafe { core::slice::from_raw_parts_mut(ptr, len) })
+    }
+
+    /// Copy a string into the arena. Returns [None] if the arena is full
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_str(&self, s: &str) -> Option<&mut str> {
+        let bytes = self.alloc_slice(s.len(), 0u8)?;
+        bytes.copy_from_slice(s.as_bytes());
+
+        Some(unsafe { str::from_utf8_unchecked_mut(bytes) })
+    }
+
+    /// The amount of bytes handed out since the last reset, padding included
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// Free everything at once. In debug builds the freed memory is filled with [POISON].
+    ///
+    /// # Safety
+    /// Nothing allocated from the arena can be used afterwards
+    pub unsafe fn reset(&self) {
+        #[cfg(debug_assertions)]
+        if self.used() > 0 {
+            unsafe { self.base().write_bytes(POISON, self.used()) };
+        }
+
+        self.used.set(0);
+    }
+}
+
+impl Default for FrameArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Wasm has only one thread
+unsafe impl Sync for FrameArena {}
+unsafe impl Send for FrameArena {}
+
+static FRAME_ARENA: FrameArena = FrameArena::new();
+
+/// Use the global frame arena. Whatever is allocated can't escape the closure, since the arena
+/// is reset after every frame
+/// ```ignore
+/// with_frame_arena(|arena| {
+///     let vertices = arena.alloc_slice(sprites.len() * 8, 0.0f32)?;
+///     ...
+/// });
+/// ```
+pub fn with_frame_arena<R>(f: impl FnOnce(&FrameArena) -> R) -> R {
+    f(&FRAME_ARENA)
+}
+
+/// Reset the global frame arena. Called by [crate::make_app] after every `__draw`
+///
+/// # Safety
+/// Can't be called from inside of [with_frame_arena]
+pub unsafe fn reset_frame_arena() {
+    unsafe { FRAME_ARENA.reset() };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_arena() {
+        let arena = FrameArena::new();
+
+        let bytes = arena.alloc_slice(3, 1u8).unwrap();
+        let floats = arena.alloc_slice(2, 0.5f32).unwrap();
+        let text = arena.alloc_str("score: 10").unwrap();
+
+        assert_eq!(bytes, [1; 3]);
+        assert_eq!(floats, [0.5; 2]);
+        assert_eq!(text, "score: 10");
+
+        // The floats were aligned past the bytes
+        assert_eq!(floats.as_ptr() as usize % 4, 0);
+        assert_eq!(arena.used(), 4 + 8 + 9);
+
+        // Too big
+        assert!(arena.alloc_slice(FRAME_ARENA_SIZE, 0u8).is_none());
+
+        let offset = floats.as_ptr() as usize - arena.base() as usize;
+        unsafe { arena.reset() };
+        assert_eq!(arena.used(), 0);
+
+        // Poisoned
+        #[cfg(debug_assertions)]
+        assert_eq!(unsafe { *arena.base().add(offset) }, POISON);
+
+        // And reused from the start
+        let bytes = arena.alloc_slice(FRAME_ARENA_SIZE, 0u8).unwrap();
+        assert_eq!(bytes.len(), FRAME_ARENA_SIZE);
+    }
+}
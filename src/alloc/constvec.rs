@@ -23,14 +23,17 @@ impl<T, const S: usize> ConstVec<T, S> {
         self.length
     }
 
+    // NOTE: Indexing goes through `get_unchecked` after checking the length by hand, since
+    // bounds checks link in core's panic formatting, which costs kilobytes
+
     pub fn as_slice(&self) -> &[T] {
         let len = self.len();
-        unsafe { transmute(&self.items[0..len]) }
+        unsafe { transmute(self.items.get_unchecked(0..len)) }
     } 
 
     pub fn as_mut_slice(&mut self) -> &mut [T] {
         let len = self.len();
-        unsafe { transmute(&mut self.items[0..len]) }
+        unsafe { transmute(self.items.get_unchecked_mut(0..len)) }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -49,7 +52,7 @@ impl<T, const S: usize> ConstVec<T, S> {
     /// which is... interesting.
     pub fn push(&mut self, item: T) {
         if !self.is_full() { 
-            self.items[self.length].write(item);
+            unsafe { self.items.get_unchecked_mut(self.length) }.write(item);
             self.length += 1;
         }
     }
@@ -64,7 +67,7 @@ impl<T, const S: usize> ConstVec<T, S> {
         self.length -= 1;
         
         let item = core::mem::replace(
-            &mut self.items[self.length], 
+            unsafe { self.items.get_unchecked_mut(self.length) }, 
             MaybeUninit::uninit()
         );
 
@@ -76,13 +79,9 @@ impl<T, const S: usize> ConstVec<T, S> {
             return None;
         }
 
-        if self.length > 1 {
-            let [current, last] = self.items
-                .get_disjoint_mut([index, self.length-1])
-                .unwrap();
-    
-            core::mem::swap(current, last);
-        }
+        // `ptr::swap` is fine with both being the same slot
+        let items = self.items.as_mut_ptr();
+        unsafe { core::ptr::swap(items.add(index), items.add(self.length-1)) };
 
         self.pop()
     }
@@ -96,7 +95,7 @@ impl<T, const S: usize> ConstVec<T, S> {
         }
 
         let item = core::mem::replace(
-            &mut self.items[index], 
+            unsafe { self.items.get_unchecked_mut(index) }, 
             MaybeUninit::new(value)
         );
 
@@ -109,7 +108,7 @@ impl<T, const S: usize> ConstVec<T, S> {
         }
 
         Some(
-            unsafe { self.items[index].assume_init_ref() }
+            unsafe { self.items.get_unchecked(index).assume_init_ref() }
         )
     }
 
@@ -119,7 +118,7 @@ impl<T, const S: usize> ConstVec<T, S> {
         }
 
         Some(
-            unsafe { self.items[index].assume_init_mut() }
+            unsafe { self.items.get_unchecked_mut(index).assume_init_mut() }
         )
     }
 
@@ -130,6 +129,12 @@ impl<T, const S: usize> ConstVec<T, S> {
     }
 }
 
+impl<T, const S: usize> Default for ConstVec<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T, const S: usize> Drop for ConstVec<T, S> {
     fn drop(&mut self) {
         self.clear();
@@ -151,6 +156,7 @@ impl<T, const S: usize> IndexMut<usize> for ConstVec<T, S> {
 }
 
 #[cfg(test)]
+#[allow(clippy::let_unit_value)]
 mod tests {
     use core::ops::AddAssign;
 
@@ -0,0 +1,37 @@
+//! A [GlobalAlloc] on top of our own allocator, so that `alloc` collections (`Vec`, `Box` and
+//! so on) can be used in builds that don't care about size that much.
+//!
+//! The `global-alloc` feature enables the `size-classes` backend, since a bump allocator alone
+//! would never free anything.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    ptr::{NonNull, null_mut}
+};
+
+use super::{dealloc, try_alloc, try_realloc};
+
+pub struct GlobalHeap;
+
+unsafe impl GlobalAlloc for GlobalHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { try_alloc(layout) }.map_or(null_mut(), NonNull::as_ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { dealloc(ptr, layout) };
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let Some(ptr) = NonNull::new(ptr) else {
+            return null_mut();
+        };
+
+        unsafe { try_realloc(ptr, layout, new_size) }.map_or(null_mut(), NonNull::as_ptr)
+    }
+}
+
+// Natively, tests keep using the system allocator
+#[cfg(target_family = "wasm")]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: GlobalHeap = GlobalHeap;
@@ -6,26 +6,58 @@
 //! 
 //! This however does mean that every allocation is permanent, so we should use a lot of pooling
 //! for rapid allocations.
+//!
+//! [alloc_value], [alloc_slice_fill], [alloc_slice_with] and [alloc_str] wrap that up safely,
+//! handing out references that live forever.
+//!
+//! For games where that doesn't cut it, the `size-classes` feature swaps the backend for
+//! power-of-two size classes with free lists (see [HeapBackend]), which makes [dealloc] work.
 
-use core::{alloc::Layout, cell::UnsafeCell};
+use core::{alloc::Layout, cell::UnsafeCell, ptr::NonNull};
 
 mod constvec;
 pub use constvec::ConstVec;
+
+mod arena;
+pub use arena::*;
+
+mod pool;
+pub use pool::{Handle, Pool};
+
+#[cfg(feature = "size-classes")]
+mod size_class;
+
+mod stats;
+pub use stats::*;
+
+#[cfg(feature = "global-alloc")]
+mod global;
+#[cfg(feature = "global-alloc")]
+pub use global::GlobalHeap;
     
-use crate::request_pages;
+use crate::{allocated_pages, log_error, request_pages};
 
-const PAGE_SIZE: usize = 2usize.pow(16);
+pub(crate) const PAGE_SIZE: usize = 2usize.pow(16);
 
+#[cfg(target_family = "wasm")]
 unsafe extern "C" {
     static __heap_base: u8;
 }
 
 /// Get the heap base (the address from which the heap starts) aligned to [PAGE_SIZE]
+#[cfg(target_family = "wasm")]
 pub fn heap_base() -> *const u8 {
 
     unsafe { &__heap_base as *const u8 }
 } 
 
+/// Get the heap base (the address from which the heap starts). Off-wasm this is the start of
+/// the memory simulated by [crate::host]
+#[cfg(not(target_family = "wasm"))]
+pub fn heap_base() -> *const u8 {
+    crate::host::heap_base()
+}
+
 /// Ge the initial amount of pages allocated by rust
 pub fn initial_pages() -> usize {
     (heap_base() as usize).div_ceil(PAGE_SIZE)
@@ -33,26 +65,109 @@ pub fn initial_pages() -> usize {
 
 /// Get the amount of space wasted on the first heap page
 pub fn heap_waste() -> usize {
-    let available = (initial_pages() * PAGE_SIZE) - heap_base() as usize;
-    PAGE_SIZE - available
+    waste_before(heap_base())
+}
+
+/// The space taken on the page of `base` before it (by statics and the stack)
+fn waste_before(base: *const u8) -> usize {
+    base as usize % PAGE_SIZE
+}
+
+/// The space left on the page of `base` after it, which the heap gets without growing
+fn space_after(base: *const u8) -> usize {
+    (PAGE_SIZE - waste_before(base)) % PAGE_SIZE
+}
+
+/// Where a [BumpAllocator] gets its memory from. The heap must end where the memory does, so
+/// that requested pages extend it
+pub(crate) trait MemorySource {
+    fn heap_base(&self) -> *const u8;
+
+    /// Grow the memory, returning the previous amount of pages, or [None] if it can't grow
+    fn request_pages(&mut self, pages: usize) -> Option<usize>;
+}
+
+/// The wasm linear memory, or its simulation by [crate::host] off-wasm
+pub(crate) struct LinearMemory;
+
+impl MemorySource for LinearMemory {
+    fn heap_base(&self) -> *const u8 {
+        heap_base()
+    }
+
+    fn request_pages(&mut self, pages: usize) -> Option<usize> {
+        request_pages(pages)
+    }
+}
+
+/// The strategy behind [alloc] and [dealloc]
+pub(crate) trait HeapBackend {
+    /// Returns [None] if memory couldn't grow, in which case nothing changes
+    unsafe fn alloc(&mut self, layout: Layout) -> Option<NonNull<u8>>;
+
+    unsafe fn dealloc(&mut self, ptr: NonNull<u8>, layout: Layout);
+
+    unsafe fn realloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+        new_size: usize
+    ) -> Option<NonNull<u8>> {
+        let new_layout = Layout::from_size_align(new_size, layout.align()).ok()?;
+        let new_ptr = unsafe { self.alloc(new_layout) }?;
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                ptr.as_ptr(),
+                new_ptr.as_ptr(),
+                layout.size().min(new_size)
+            );
+            self.dealloc(ptr, layout);
+        }
+
+        Some(new_ptr)
+    }
+
+    /// The amount of bytes taken from the heap so far
+    fn used(&self) -> usize;
 }
 
-struct BumpAllocator {
+#[cfg(not(feature = "size-classes"))]
+type Backend = BumpAllocator;
+
+#[cfg(feature = "size-classes")]
+type Backend = size_class::SizeClassAllocator;
+
+pub(crate) struct BumpAllocator<M: MemorySource = LinearMemory> {
+    memory: M,
     cursor: *const u8,
     free_space: usize
 }
 
 impl BumpAllocator {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
+        Self::with_memory(LinearMemory)
+    }
+}
+
+impl<M: MemorySource> BumpAllocator<M> {
+    pub(crate) fn with_memory(memory: M) -> Self {
+        let cursor = memory.heap_base();
+
         Self {
-            cursor: heap_base(),
-            free_space: PAGE_SIZE-heap_waste()
+            memory,
+            cursor,
+            free_space: space_after(cursor)
         }
     }
+}
 
-    unsafe fn alloc(&mut self, layout: core::alloc::Layout) -> *mut u8 {
-        // Compute the padding to align user data
-        let padding = self.cursor.align_offset(layout.align());
+impl<M: MemorySource> HeapBackend for BumpAllocator<M> {
+    unsafe fn alloc(&mut self, layout: Layout) -> Option<NonNull<u8>> {
+        // Compute the padding to align user data. Unlike `align_offset`, this is exact under
+        // miri too
+        let cursor = self.cursor as usize;
+        let padding = cursor.next_multiple_of(layout.align()) - cursor;
 
         // Compute the total amount of data that needs to be put
         let total = padding+layout.size();
@@ -64,12 +179,15 @@ impl BumpAllocator {
             let needs_pages = (total-self.free_space).div_ceil(PAGE_SIZE);
             
             // Grow our memory
-            request_pages(needs_pages);
+            self.memory.request_pages(needs_pages)?;
+            record_pages(needs_pages);
 
             // Increase the amount of free space
             self.free_space += needs_pages * PAGE_SIZE;
         }
 
+        record_padding(padding);
+
         // Get our return pointer
         let ptr = unsafe { self.cursor.add(padding) } as *mut u8;
 
@@ -79,12 +197,19 @@ impl BumpAllocator {
         // Decrease the amount of free space we got
         self.free_space -= total;
 
-        ptr
+        NonNull::new(ptr)
+    }
+
+    /// Memory is permanent
+    unsafe fn dealloc(&mut self, _ptr: NonNull<u8>, _layout: Layout) {}
+
+    fn used(&self) -> usize {
+        self.cursor as usize - self.memory.heap_base() as usize
     }
 }
 
 struct Allocator {
-    alloc: UnsafeCell<Option<BumpAllocator>>
+    alloc: UnsafeCell<Option<Backend>>
 }
 
 // We're in wasm, so we don't care
@@ -98,27 +223,106 @@ impl Allocator {
         }
     }
 
-    unsafe fn get_alloc(&self) -> &mut BumpAllocator {
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn get_alloc(&self) -> &mut Backend {
         let alloc = unsafe { &mut *self.alloc.get() };
 
         if alloc.is_none() {
-            alloc.replace(BumpAllocator::new());
+            alloc.replace(Backend::new());
         }
 
         alloc.as_mut().unwrap()
     }
-
-    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        unsafe { self.get_alloc().alloc(layout) }
-    }
 }
 
 static ALLOCATOR: Allocator = Allocator::new();
 
-/// Allocate the provided amount of memory. Note that this memory is permanent and can't
-/// be deallocated.
+/// Allocate the provided amount of memory, or return [None] if memory can't grow anymore.
+/// Note that unless the `size-classes` feature is enabled, this memory is permanent.
+///
+/// # Safety
+/// The returned memory is uninitialized
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub unsafe fn try_alloc(layout: Layout) -> Option<NonNull<u8>> {
+    // Tests allocate from several threads at once
+    #[cfg(not(target_family = "wasm"))]
+    let _lock = crate::host::lock_heap();
+
+    let ptr = unsafe { ALLOCATOR.get_alloc().alloc(layout) }?;
+    record_alloc(layout.size());
+
+    Some(ptr)
+}
+
+/// Allocate the provided amount of memory. Note that unless the `size-classes` feature is
+/// enabled, this memory is permanent.
+///
+/// Running out of memory calls [on_out_of_memory]
+///
+/// # Safety
+/// The returned memory is uninitialized
+#[cfg_attr(feature = "heap-stats", track_caller)]
 pub unsafe fn alloc(layout: Layout) -> *mut u8 {
-    unsafe { ALLOCATOR.alloc(layout) }
+    match unsafe { try_alloc(layout) } {
+        Some(ptr) => ptr.as_ptr(),
+        None => on_out_of_memory(layout)
+    }
+}
+
+/// Give memory back to the allocator. Does nothing unless the `size-classes` feature is enabled
+///
+/// # Safety
+/// `ptr` must have been allocated with `layout`, and can't be used afterwards
+pub unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+    #[cfg(not(target_family = "wasm"))]
+    let _lock = crate::host::lock_heap();
+
+    if let Some(ptr) = NonNull::new(ptr) {
+        unsafe { ALLOCATOR.get_alloc().dealloc(ptr, layout) };
+
+        // The bump allocator keeps the memory anyway
+        #[cfg(feature = "size-classes")]
+        record_dealloc(layout.size());
+    }
+}
+
+/// Resize an allocation, moving it if needed. Returns [None] (leaving the allocation as is) if
+/// memory can't grow anymore
+///
+/// # Safety
+/// `ptr` must have been allocated with `layout`, and can't be used afterwards unless this fails
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub unsafe fn try_realloc(ptr: NonNull<u8>, layout: Layout, new_size: usize) -> Option<NonNull<u8>> {
+    #[cfg(not(target_family = "wasm"))]
+    let _lock = crate::host::lock_heap();
+
+    let new_ptr = unsafe { ALLOCATOR.get_alloc().realloc(ptr, layout, new_size) }?;
+
+    #[cfg(feature = "size-classes")]
+    record_dealloc(layout.size());
+    record_alloc(new_size);
+
+    Some(new_ptr)
+}
+
+/// The amount of bytes taken from the heap so far, padding included
+pub fn heap_used() -> usize {
+    #[cfg(not(target_family = "wasm"))]
+    let _lock = crate::host::lock_heap();
+
+    unsafe { ALLOCATOR.get_alloc() }.used()
+}
+
+/// Called when [alloc] runs out of memory. Reports the failed allocation and the state of the
+/// heap to the console, then aborts
+#[cold]
+pub fn on_out_of_memory(layout: Layout) -> ! {
+    log_error!(
+        "Out of memory: couldn't allocate ", layout.size(), " bytes (aligned to ", layout.align(),
+        ") with ", heap_used(), " bytes of heap in use over ", allocated_pages(), " pages"
+    );
+
+    panic!("Out of memory");
 }
 
 pub fn layouts_of<T>(amount: usize) -> Layout {
@@ -130,4 +334,141 @@ pub fn layouts_of<T>(amount: usize) -> Layout {
 
 pub fn layout_of<T>() -> Layout {
     layouts_of::<T>(1)
-}
\ No newline at end of file
+}
+/// Move a value into a permanent allocation. Like `Box::leak`, the lifetime can be anything,
+/// `'static` included
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub fn alloc_value<'a, T>(value: T) -> &'a mut T {
+    let ptr = unsafe { alloc(layout_of::<T>()) } as *mut T;
+
+    unsafe {
+        ptr.write(value);
+        &mut *ptr
+    }
+}
+
+/// Allocate a permanent slice of `len` copies of `value`
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub fn alloc_slice_fill<'a, T: Copy>(len: usize, value: T) -> &'a mut [T] {
+    alloc_slice_with(len, |_| value)
+}
+
+/// Allocate a permanent slice, with every element built by `f` from its index. Panics if the
+/// size of the slice overflows
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub fn alloc_slice_with<'a, T>(len: usize, mut f: impl FnMut(usize) -> T) -> &'a mut [T] {
+    let Ok(layout) = Layout::array::<T>(len) else {
+        panic!("Allocation too large");
+    };
+
+    let ptr = unsafe { alloc(layout) } as *mut T;
+
+    for index in 0..len {
+        unsafe { ptr.add(index).write(f(index)) };
+    }
+
+    unsafe { core::slice::from_raw_parts_mut(ptr, len) }
+}
+
+/// Copy a string into a permanent allocation
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub fn alloc_str(s: &str) -> &'static str {
+    let bytes = alloc_slice_fill(s.len(), 0u8);
+    bytes.copy_from_slice(s.as_bytes());
+
+    unsafe { str::from_utf8_unchecked(bytes) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::TestMemory;
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn test_heap_waste() {
+        let at = |address| core::ptr::without_provenance::<u8>(address);
+
+        let base = at(3 * PAGE_SIZE + 100);
+        assert_eq!((waste_before(base), space_after(base)), (100, PAGE_SIZE - 100));
+
+        // An aligned heap wastes nothing, but has to grow right away
+        assert_eq!((waste_before(at(3 * PAGE_SIZE)), space_after(at(3 * PAGE_SIZE))), (0, 0));
+
+        // The simulated heap is aligned
+        assert_eq!(heap_waste(), 0);
+    }
+
+    #[test]
+    fn test_bump_padding() {
+        let mut bump = BumpAllocator::with_memory(TestMemory::new(101, 1));
+        let base = bump.memory.heap_base();
+
+        let a = unsafe { bump.alloc(layout(1, 1)) }.unwrap();
+        let b = unsafe { bump.alloc(layout(8, 8)) }.unwrap();
+        let c = unsafe { bump.alloc(layout(2, 2)) }.unwrap();
+
+        // 102 is padded to 104 for `b`, which ends at 112, where `c` fits without padding
+        assert_eq!(a.as_ptr() as *const u8, base);
+        assert_eq!(b.as_ptr() as usize - base as usize, 3);
+        assert_eq!(c.as_ptr() as usize - base as usize, 11);
+        assert_eq!(bump.used(), 13);
+
+        // Padding counts as well, so aligning past the end of the memory fails
+        assert!(unsafe { bump.alloc(layout(1, 2 * PAGE_SIZE)) }.is_none());
+        assert_eq!(bump.used(), 13);
+    }
+
+    #[test]
+    fn test_bump_growth() {
+        let mut bump = BumpAllocator::with_memory(TestMemory::new(PAGE_SIZE - 16, 4));
+
+        // Exactly the rest of the first page
+        unsafe { bump.alloc(layout(16, 8)) }.unwrap();
+        assert_eq!(bump.memory.pages(), 1);
+
+        // Crossing into the next page
+        unsafe { bump.alloc(layout(1, 1)) }.unwrap();
+        assert_eq!(bump.memory.pages(), 2);
+
+        // Needs two more pages, as one byte of the second page is taken
+        let big = unsafe { bump.alloc(layout(2 * PAGE_SIZE, 1)) }.unwrap();
+        assert_eq!(bump.memory.pages(), 4);
+        unsafe { big.write_bytes(1, 2 * PAGE_SIZE) };
+
+        // Growing past the memory fails without changing anything
+        let used = bump.used();
+        assert!(unsafe { bump.alloc(layout(PAGE_SIZE, 1)) }.is_none());
+        assert_eq!((bump.used(), bump.memory.pages()), (used, 4));
+
+        // But whatever is left can still be used
+        unsafe { bump.alloc(layout(PAGE_SIZE - 1, 1)) }.unwrap();
+        assert_eq!(bump.memory.pages(), 4);
+    }
+
+    #[test]
+    fn test_typed_alloc() {
+        let value: &'static mut [u16; 3] = alloc_value([1, 2, 3]);
+        value[1] = 5;
+        assert_eq!(*value, [1, 5, 3]);
+
+        let filled = alloc_slice_fill(4, 0.5f64);
+        assert_eq!(filled, [0.5; 4]);
+        assert_eq!(filled.as_ptr() as usize % align_of::<f64>(), 0);
+
+        let squares = alloc_slice_with(4, |index| index * index);
+        assert_eq!(squares, [0, 1, 4, 9]);
+
+        assert_eq!(alloc_str("high score"), "high score");
+        assert_eq!(alloc_slice_fill(0, 0u8), []);
+    }
+
+    #[test]
+    #[should_panic(expected = "Allocation too large")]
+    fn test_alloc_slice_overflow() {
+        alloc_slice_fill(usize::MAX / 4 + 2, 0u32);
+    }
+}
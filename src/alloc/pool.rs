@@ -0,0 +1,283 @@
+//! Generational object pools, for values that are created and destroyed all the time.
+//!
+//! The bump heap never frees anything, so instead of allocating every projectile anew, a [Pool]
+//! reserves its slots once and recycles them. Values are referred to through [Handle]s, which
+//! carry the generation of their slot and so go stale once the value is removed.
+
+use core::marker::PhantomData;
+
+use super::alloc_slice_with;
+
+/// Marks the end of the free list
+const NONE: u32 = u32::MAX;
+
+/// A reference to a value in a [Pool]. Handles of removed values stay invalid, even after their
+/// slot is reused
+pub struct Handle<T> {
+    index: u32,
+    generation: u32,
+    _marker: PhantomData<fn() -> T>
+}
+
+impl<T> Handle<T> {
+    /// The index of the slot the value lives in
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+}
+
+// Derives would require `T` to implement these too
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> core::fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Handle({}v{})", self.index, self.generation)
+    }
+}
+
+enum SlotState<T> {
+    Occupied(T),
+
+    /// The next free slot, making up the free list
+    Free(u32)
+}
+
+struct Slot<T> {
+    generation: u32,
+    state: SlotState<T>
+}
+
+/// A fixed amount of slots for values that come and go all the time (projectiles, particles
+/// and so on). The slots are reserved from the bump heap once, and freed slots are reused in
+/// O(1) through a free list stored inside of them.
+pub struct Pool<T> {
+    slots: *mut Slot<T>,
+    capacity: u32,
+    len: u32,
+    free: u32
+}
+
+impl<T> Pool<T> {
+    /// Reserve `capacity` slots. Note that this memory is permanent, so pools should be
+    /// created once
+    #[cfg_attr(feature = "heap-stats", track_caller)]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.min(NONE as usize) as u32;
+        let slots = alloc_slice_with(capacity as usize, |index| Slot {
+            generation: 0,
+            state: SlotState::Free(if index + 1 < capacity as usize { index as u32 + 1 } else { NONE })
+        });
+
+        Self {
+            slots: slots.as_mut_ptr(),
+            capacity,
+            len: 0,
+            free: if capacity > 0 { 0 } else { NONE }
+        }
+    }
+
+    fn slots(&self) -> &[Slot<T>] {
+        unsafe { core::slice::from_raw_parts(self.slots, self.capacity as usize) }
+    }
+
+    fn slots_mut(&mut self) -> &mut [Slot<T>] {
+        unsafe { core::slice::from_raw_parts_mut(self.slots, self.capacity as usize) }
+    }
+
+    /// The amount of values in the pool
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.free == NONE
+    }
+
+    /// Put a value into a free slot. Returns it back if the pool is full
+    pub fn insert(&mut self, value: T) -> Result<Handle<T>, T> {
+        let index = self.free;
+        let Some(slot) = self.slots_mut().get_mut(index as usize) else {
+            return Err(value);
+        };
+
+        let SlotState::Free(next) = slot.state else {
+            unreachable!()
+        };
+
+        slot.state = SlotState::Occupied(value);
+        let generation = slot.generation;
+
+        self.free = next;
+        self.len += 1;
+
+        Ok(Handle { index, generation, _marker: PhantomData })
+    }
+
+    fn slot_mut(&mut self, handle: Handle<T>) -> Option<&mut Slot<T>> {
+        let slot = self.slots_mut().get_mut(handle.index as usize)?;
+
+        (slot.generation == handle.generation).then_some(slot)
+    }
+
+    /// Take a value out of the pool, freeing its slot
+    pub fn remove(&mut self, handle: Handle<T>) -> Option<T> {
+        let free = self.free;
+        let slot = self.slot_mut(handle)?;
+
+        if let SlotState::Free(_) = slot.state {
+            return None;
+        }
+
+        let SlotState::Occupied(value) = core::mem::replace(&mut slot.state, SlotState::Free(free))
+        else {
+            unreachable!()
+        };
+        slot.generation = slot.generation.wrapping_add(1);
+
+        self.free = handle.index;
+        self.len -= 1;
+
+        Some(value)
+    }
+
+    pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+        let slot = self.slots().get(handle.index as usize)?;
+
+        match &slot.state {
+            SlotState::Occupied(value) if slot.generation == handle.generation => Some(value),
+            _ => None
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        match &mut self.slot_mut(handle)?.state {
+            SlotState::Occupied(value) => Some(value),
+            SlotState::Free(_) => None
+        }
+    }
+
+    /// Iterate over every value along with its handle
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.slots().iter().enumerate().filter_map(|(index, slot)| match &slot.state {
+            SlotState::Occupied(value) => Some((
+                Handle { index: index as u32, generation: slot.generation, _marker: PhantomData },
+                value
+            )),
+            SlotState::Free(_) => None
+        })
+    }
+
+    /// Iterate mutably over every value along with its handle
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Handle<T>, &mut T)> {
+        self.slots_mut().iter_mut().enumerate().filter_map(|(index, slot)| match &mut slot.state {
+            SlotState::Occupied(value) => Some((
+                Handle { index: index as u32, generation: slot.generation, _marker: PhantomData },
+                value
+            )),
+            SlotState::Free(_) => None
+        })
+    }
+
+    /// Only keep the values `keep` returns `true` for
+    pub fn retain(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        let mut free = self.free;
+        let mut len = self.len;
+
+        for (index, slot) in self.slots_mut().iter_mut().enumerate() {
+            if let SlotState::Occupied(value) = &mut slot.state && !keep(value) {
+                // Drops the value, and pushes the slot onto the free list
+                slot.state = SlotState::Free(free);
+                slot.generation = slot.generation.wrapping_add(1);
+
+                free = index as u32;
+                len -= 1;
+            }
+        }
+
+        self.free = free;
+        self.len = len;
+    }
+
+    /// Remove every value
+    pub fn clear(&mut self) {
+        self.retain(|_| false);
+    }
+}
+
+impl<T> Drop for Pool<T> {
+    fn drop(&mut self) {
+        // The slots themselves can't be freed, but the values might own something
+        if core::mem::needs_drop::<T>() {
+            self.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Bullet {
+        x: f32
+    }
+
+    #[test]
+    fn test_pool() {
+        let mut pool = Pool::new(3);
+
+        let a = pool.insert(Bullet { x: 1.0 }).unwrap();
+        let b = pool.insert(Bullet { x: 2.0 }).unwrap();
+        let c = pool.insert(Bullet { x: 3.0 }).unwrap();
+
+        assert!(pool.is_full());
+        assert_eq!(pool.insert(Bullet { x: 4.0 }), Err(Bullet { x: 4.0 }));
+
+        pool.get_mut(b).unwrap().x = 5.0;
+        assert_eq!(pool.remove(b), Some(Bullet { x: 5.0 }));
+        assert_eq!(pool.len(), 2);
+
+        // The slot is reused, but the old handle stays invalid
+        let d = pool.insert(Bullet { x: 6.0 }).unwrap();
+        assert_eq!(d.index(), b.index());
+        assert_eq!(pool.get(b), None);
+        assert_eq!(pool.remove(b), None);
+        assert_eq!(pool.get(d), Some(&Bullet { x: 6.0 }));
+
+        pool.retain(|bullet| bullet.x < 5.0);
+        assert_eq!(pool.len(), 2);
+
+        let handles: [_; 2] = core::array::from_fn(|i| pool.iter().nth(i).unwrap().0);
+        assert_eq!(handles, [a, c]);
+
+        // The slot went back onto the free list
+        assert_eq!(pool.insert(Bullet { x: 7.0 }).map(|e| e.index()), Ok(d.index()));
+        assert!(pool.is_full());
+
+        pool.clear();
+        assert!(pool.is_empty());
+        assert_eq!(pool.get(a), None);
+    }
+}
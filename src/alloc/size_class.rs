@@ -0,0 +1,165 @@
+//! Segregated power-of-two size classes on top of the bump allocator.
+//!
+//! Blocks are rounded up to a power of two and aligned to their own size (up to a page). Freed
+//! blocks go into a free list per size class, and get reused by the next allocation of that
+//! class. Memory never goes back to the bump allocator itself, which keeps growing through
+//! `request_pages` whenever a class runs dry.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+use super::{
+    BumpAllocator, HeapBackend, LinearMemory, MemorySource, PAGE_SIZE, record_padding,
+    record_padding_freed
+};
+
+/// One size class for every power of two
+const CLASSES: usize = usize::BITS as usize;
+
+/// A freed block, linked into the free list of its class
+struct FreeBlock {
+    next: *mut FreeBlock
+}
+
+pub(crate) struct SizeClassAllocator<M: MemorySource = LinearMemory> {
+    bump: BumpAllocator<M>,
+    free: [*mut FreeBlock; CLASSES]
+}
+
+impl SizeClassAllocator {
+    pub(crate) fn new() -> Self {
+        Self::with_bump(BumpAllocator::new())
+    }
+}
+
+impl<M: MemorySource> SizeClassAllocator<M> {
+    pub(crate) fn with_bump(bump: BumpAllocator<M>) -> Self {
+        Self {
+            bump,
+            free: [core::ptr::null_mut(); CLASSES]
+        }
+    }
+
+    /// The size class of a layout. Layouts aligned to more than a page don't have one, and are
+    /// never reused
+    fn class(layout: Layout) -> Option<usize> {
+        if layout.align() > PAGE_SIZE {
+            return None;
+        }
+
+        let size = layout.size()
+            .max(layout.align())
+            .max(size_of::<FreeBlock>())
+            .checked_next_power_of_two()?;
+
+        Some(size.trailing_zeros() as usize)
+    }
+
+    /// The bytes a block of `class` wastes on top of `size`
+    fn rounding(class: usize, size: usize) -> usize {
+        (1 << class) - size
+    }
+
+    fn block_layout(class: usize) -> Layout {
+        let size = 1 << class;
+
+        Layout::from_size_align(size, size.min(PAGE_SIZE)).unwrap()
+    }
+}
+
+impl<M: MemorySource> HeapBackend for SizeClassAllocator<M> {
+    unsafe fn alloc(&mut self, layout: Layout) -> Option<NonNull<u8>> {
+        let Some(class) = Self::class(layout) else {
+            return unsafe { self.bump.alloc(layout) };
+        };
+
+        record_padding(Self::rounding(class, layout.size()));
+
+        let free = &mut self.free[class];
+        if let Some(block) = NonNull::new(*free) {
+            *free = unsafe { block.as_ref() }.next;
+
+            return Some(block.cast());
+        }
+
+        unsafe { self.bump.alloc(Self::block_layout(class)) }
+    }
+
+    unsafe fn dealloc(&mut self, ptr: NonNull<u8>, layout: Layout) {
+        // Blocks without a class are simply leaked
+        let Some(class) = Self::class(layout) else {
+            return;
+        };
+
+        record_padding_freed(Self::rounding(class, layout.size()));
+
+        let block = ptr.cast::<FreeBlock>();
+        unsafe { block.write(FreeBlock { next: self.free[class] }) };
+
+        self.free[class] = block.as_ptr();
+    }
+
+    unsafe fn realloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+        new_size: usize
+    ) -> Option<NonNull<u8>> {
+        let new_layout = Layout::from_size_align(new_size, layout.align()).ok()?;
+
+        // The block is already big enough (and not too big)
+        if let Some(class) = Self::class(layout) && Self::class(new_layout) == Some(class) {
+            record_padding_freed(Self::rounding(class, layout.size()));
+            record_padding(Self::rounding(class, new_size));
+
+            return Some(ptr);
+        }
+
+        let new_ptr = unsafe { self.alloc(new_layout) }?;
+        unsafe {
+            core::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+
+        Some(new_ptr)
+    }
+
+    fn used(&self) -> usize {
+        self.bump.used()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_classes() {
+        // A memory of our own, so that the global heap isn't touched
+        let mut heap = SizeClassAllocator::with_bump(
+            BumpAllocator::with_memory(crate::host::TestMemory::new(0, 1))
+        );
+        let small = Layout::from_size_align(24, 8).unwrap();
+
+        let a = unsafe { heap.alloc(small) }.unwrap();
+        assert_eq!(a.as_ptr() as usize % 32, 0);
+
+        // Freed blocks are reused by any layout of the same class
+        unsafe { heap.dealloc(a, small) };
+        let b = unsafe { heap.alloc(Layout::from_size_align(32, 4).unwrap()) }.unwrap();
+        assert_eq!(a, b);
+
+        let c = unsafe { heap.alloc(small) }.unwrap();
+        assert_ne!(b, c);
+
+        // Shrinking (or growing) within the class keeps the block
+        unsafe { c.write_bytes(7, 24) };
+        assert_eq!(unsafe { heap.realloc(c, small, 17) }, Some(c));
+
+        let d = unsafe { heap.realloc(c, small, 100) }.unwrap();
+        assert_ne!(c, d);
+        assert_eq!(unsafe { core::slice::from_raw_parts(d.as_ptr(), 24) }, [7; 24]);
+
+        // The old block went back into its free list
+        assert_eq!(unsafe { heap.alloc(small) }, Some(c));
+    }
+}
@@ -0,0 +1,240 @@
+//! Heap telemetry, enabled with the `heap-stats` feature. Without it, recording compiles down to
+//! nothing.
+//!
+//! Allocations are also counted per call site (through `#[track_caller]`), so that the report
+//! shows where memory goes. In the browser console, `heapReport()` prints it.
+
+#[cfg(feature = "heap-stats")]
+use core::panic::Location;
+
+#[cfg(feature = "heap-stats")]
+use crate::{ConstVec, StackStr, StaticCell, println};
+
+/// The maximum amount of call sites tracked separately
+pub const MAX_HEAP_SITES: usize = 32;
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct HeapStats {
+    /// The bytes currently allocated, as requested by the callers
+    pub allocated: usize,
+
+    /// The highest [HeapStats::allocated] has ever been
+    pub peak: usize,
+
+    /// The bytes lost to alignment padding, and to rounding blocks up to their size class with
+    /// the `size-classes` backend
+    pub padding: usize,
+
+    /// The pages the allocator requested from JS
+    pub pages_requested: usize,
+
+    pub allocations: usize,
+    pub deallocations: usize,
+
+    /// Allocations from call sites that didn't fit into the site table
+    pub untracked_allocations: usize
+}
+
+/// Allocations made from a single call site
+#[derive(Clone, Copy, Debug)]
+pub struct SiteStats {
+    pub file: &'static str,
+    pub line: u32,
+    pub allocations: usize,
+    pub bytes: usize
+}
+
+#[cfg(feature = "heap-stats")]
+static STATS: StaticCell<HeapStats> = StaticCell::new(HeapStats {
+    allocated: 0,
+    peak: 0,
+    padding: 0,
+    pages_requested: 0,
+    allocations: 0,
+    deallocations: 0,
+    untracked_allocations: 0
+});
+
+#[cfg(feature = "heap-stats")]
+static SITES: StaticCell<ConstVec<SiteStats, MAX_HEAP_SITES>> = StaticCell::new(ConstVec::new());
+
+#[cfg(feature = "heap-stats")]
+fn stats() -> &'static mut HeapStats {
+    unsafe { STATS.get_mut() }
+}
+
+#[cfg_attr(feature = "heap-stats", track_caller)]
+pub(crate) fn record_alloc(size: usize) {
+    #[cfg(feature = "heap-stats")]
+    {
+        let stats = stats();
+        stats.allocations += 1;
+        stats.allocated += size;
+        stats.peak = stats.peak.max(stats.allocated);
+
+        let location = Location::caller();
+        let sites = unsafe { SITES.get_mut() };
+        let index = sites.as_slice()
+            .iter()
+            .position(|site| site.line == location.line() && site.file == location.file());
+
+        match index {
+            Some(index) => {
+                let site = &mut sites.as_mut_slice()[index];
+                site.allocations += 1;
+                site.bytes += size;
+            },
+            None if !sites.is_full() => sites.push(SiteStats {
+                file: location.file(),
+                line: location.line(),
+                allocations: 1,
+                bytes: size
+            }),
+            None => stats.untracked_allocations += 1
+        }
+    }
+
+    #[cfg(not(feature = "heap-stats"))]
+    let _ = size;
+}
+
+/// Only the `size-classes` backend actually frees memory
+#[cfg(feature = "size-classes")]
+pub(crate) fn record_dealloc(size: usize) {
+    #[cfg(feature = "heap-stats")]
+    {
+        let stats = stats();
+        stats.deallocations += 1;
+        stats.allocated -= size;
+    }
+
+    #[cfg(not(feature = "heap-stats"))]
+    let _ = size;
+}
+
+pub(crate) fn record_padding(padding: usize) {
+    #[cfg(feature = "heap-stats")]
+    {
+        stats().padding += padding;
+    }
+
+    #[cfg(not(feature = "heap-stats"))]
+    let _ = padding;
+}
+
+/// Freed size-class blocks don't waste their rounding anymore
+#[cfg(feature = "size-classes")]
+pub(crate) fn record_padding_freed(padding: usize) {
+    #[cfg(feature = "heap-stats")]
+    {
+        stats().padding -= padding;
+    }
+
+    #[cfg(not(feature = "heap-stats"))]
+    let _ = padding;
+}
+
+pub(crate) fn record_pages(pages: usize) {
+    #[cfg(feature = "heap-stats")]
+    {
+        stats().pages_requested += pages;
+    }
+
+    #[cfg(not(feature = "heap-stats"))]
+    let _ = pages;
+}
+
+/// A snapshot of the heap statistics
+#[cfg(feature = "heap-stats")]
+pub fn heap_stats() -> HeapStats {
+    #[cfg(not(target_family = "wasm"))]
+    let _lock = crate::host::lock_heap();
+
+    *stats()
+}
+
+/// Call the provided function with the statistics of every tracked call site
+#[cfg(feature = "heap-stats")]
+pub fn for_each_heap_site(mut f: impl FnMut(&SiteStats)) {
+    let sites = {
+        #[cfg(not(target_family = "wasm"))]
+        let _lock = crate::host::lock_heap();
+
+        let sites: &ConstVec<SiteStats, MAX_HEAP_SITES> = unsafe { SITES.get() };
+        let mut copy: ConstVec<SiteStats, MAX_HEAP_SITES> = ConstVec::new();
+        for site in sites.as_slice() {
+            copy.push(*site);
+        }
+
+        copy
+    };
+
+    sites.as_slice().iter().for_each(&mut f);
+}
+
+/// Render a line of the report and print it
+#[cfg(feature = "heap-stats")]
+macro_rules! report {
+    ($($part:expr),+ $(,)?) => {{
+        let mut line: StackStr<256> = StackStr::new();
+        $( crate::Render::render(&$part, &mut line); )+
+
+        println(line.as_str());
+    }};
+}
+
+/// Print the heap statistics and the allocations of every call site to the console
+#[cfg(feature = "heap-stats")]
+pub fn print_heap_report() {
+    let stats = heap_stats();
+
+    report!(
+        "Heap: ", stats.allocated, " bytes allocated (peak ", stats.peak, ") in ",
+        stats.allocations, " allocations, ", stats.deallocations, " deallocations, ",
+        stats.padding, " bytes of padding, ", stats.pages_requested, " pages requested"
+    );
+
+    for_each_heap_site(|site| {
+        report!("  ", site.file, ":", site.line, " - ", site.allocations, " allocations, ", site.bytes, " bytes");
+    });
+
+    if stats.untracked_allocations > 0 {
+        report!("  (", stats.untracked_allocations, " allocations from other sites)");
+    }
+}
+
+/// Print the heap report. Exported here rather than by [crate::make_app], so that it only
+/// exists with the `heap-stats` feature
+#[cfg(feature = "heap-stats")]
+#[unsafe(export_name = crate::wasm_name!(__heap_report))]
+pub extern "C" fn __heap_report() {
+    print_heap_report();
+}
+
+#[cfg(all(test, feature = "heap-stats"))]
+mod tests {
+    use super::*;
+    use crate::{alloc, layouts_of};
+
+    #[test]
+    fn test_heap_stats() {
+        let line = line!() + 1;
+        unsafe { alloc(layouts_of::<u64>(3)) };
+
+        let mut site = None;
+        for_each_heap_site(|s| if s.file == file!() && s.line == line {
+            site = Some(*s);
+        });
+
+        let site = site.unwrap();
+        assert_eq!((site.allocations, site.bytes), (1, 24));
+
+        let stats = heap_stats();
+        assert!(stats.allocations >= 1);
+        assert!(stats.peak >= stats.allocated && stats.allocated >= 24);
+
+        // The 24 bytes were rounded up to a 32 byte block
+        #[cfg(feature = "size-classes")]
+        assert!(stats.padding >= 8);
+    }
+}
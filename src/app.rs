@@ -1,28 +1,100 @@
 #[cfg(target_family = "wasm")]
 #[panic_handler]
 fn panic_handler(info: &core::panic::PanicInfo) -> ! {
-    let msg = info.message().as_str();
+    let mut msg: crate::StackStr<{ crate::PANIC_MESSAGE_SIZE }> = crate::StackStr::new();
+    crate::write_panic_message(&mut msg, info.message());
+
     let location = info.location().unwrap();
 
-    crate::js::panic(msg, location.file(), location.line());
+    crate::js::panic(Some(msg.as_str()), location.file(), location.line());
 
     // Unreachable
     loop {}
 }
 
+use crate::{KeyCode, MouseButton, Touch};
+
 pub trait AppHandler {
-    /// The animation loop itself (identical to update)
-    fn draw(&mut self);
+    /// The length of a single [AppHandler::update] step in seconds
+    const TIMESTEP: f32 = 1.0 / 60.0;
+
+    /// The fixed-rate game logic, called every [AppHandler::TIMESTEP] seconds
+    fn update(&mut self, _dt: f32) {}
+
+    /// The animation loop itself. `alpha` is how far we are into the next update step (`0..1`),
+    /// which can be used to interpolate between the last two states
+    fn draw(&mut self, alpha: f32);
+
+    /// The canvas was resized. `width` and `height` are in canvas pixels, and `scale` is the
+    /// device pixel ratio. The GL viewport is updated automatically before the next draw
+    fn resize(&mut self, _width: u32, _height: u32, _scale: f32) {}
+
+    /// The page was hidden or lost focus. A good place to pause the game
+    fn suspended(&mut self) {}
+
+    /// The page is visible and focused again
+    fn resumed(&mut self) {}
+
+    /// The page is about to be unloaded. This is the last chance to persist any state
+    fn before_unload(&mut self) {}
+
+    /// A key was pressed. Key repeats are not forwarded
+    fn key_down(&mut self, _key: KeyCode) {}
+
+    /// A key was released
+    fn key_up(&mut self, _key: KeyCode) {}
+
+    /// The pointer moved over the canvas. Coordinates are in canvas pixels
+    fn pointer_move(&mut self, _x: f32, _y: f32) {}
+
+    /// A mouse button was pressed over the canvas
+    fn pointer_down(&mut self, _button: MouseButton, _x: f32, _y: f32) {}
+
+    /// A mouse button was released
+    fn pointer_up(&mut self, _button: MouseButton, _x: f32, _y: f32) {}
+
+    /// The wheel was scrolled. Deltas are in pixels
+    fn wheel(&mut self, _dx: f32, _dy: f32) {}
+
+    /// A finger touched the canvas. Coordinates are in canvas pixels
+    fn touch_start(&mut self, _touch: Touch) {}
+
+    /// An active finger moved
+    fn touch_move(&mut self, _touch: Touch) {}
+
+    /// A finger was lifted
+    fn touch_end(&mut self, _touch: Touch) {}
+
+    /// The browser interrupted a touch (for example, by a system gesture)
+    fn touch_cancel(&mut self, _touch: Touch) {}
+
+    /// A gamepad was connected. Its state is available through [crate::gamepad]
+    fn gamepad_connected(&mut self, _index: usize) {}
+
+    /// A gamepad was disconnected
+    fn gamepad_disconnected(&mut self, _index: usize) {}
+}
+
+/// Called by [make_app] at the start of every frame, before the app updates
+#[doc(hidden)]
+pub fn begin_frame() {
+    #[cfg(feature = "executor")]
+    crate::run_tasks();
+
+    crate::apply_viewport();
 }
 
 /// The global macro for generating an app 
 #[macro_export]
 macro_rules! make_app {
     ($ty:ident) => {
-        static APP: InitCell<UnsafeCell<$ty>> = InitCell::new();
+        static APP: $crate::InitCell<$crate::UnsafeCell<$ty>> = $crate::InitCell::new();
+        static CLOCK: $crate::StaticCell<$crate::FrameClock> = $crate::StaticCell::new(
+            $crate::FrameClock::new(<$ty as $crate::AppHandler>::TIMESTEP)
+        );
 
         fn init_app(app: $ty) {
-            APP.init(UnsafeCell::new(app));
+            APP.init($crate::UnsafeCell::new(app));
         } 
 
         unsafe fn get_app<'a>() -> &'a mut $ty {
@@ -30,16 +102,130 @@ macro_rules! make_app {
         }
 
         
-        #[unsafe(no_mangle)]
+        #[unsafe(export_name = $crate::wasm_name!(__main))]
         pub extern "C" fn __main() {
             let app = main();
             
             init_app(app);
         }
 
-        #[unsafe(no_mangle)]
-        pub extern "C" fn __draw() {
-            unsafe { get_app() }.draw();
+        #[unsafe(export_name = $crate::wasm_name!(__draw))]
+        pub extern "C" fn __draw(time: f64) {
+            $crate::begin_frame();
+            unsafe { CLOCK.get_mut() }.run(unsafe { get_app() }, time);
+
+            // Nothing from the frame arena outlives `with_frame_arena`, so we're outside of it
+            unsafe { $crate::reset_frame_arena() };
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__resize))]
+        pub extern "C" fn __resize(width: u32, height: u32, scale: f32) {
+            $crate::dispatch_resize(unsafe { get_app() }, width, height, scale);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__suspended))]
+        pub extern "C" fn __suspended() {
+            <$ty as $crate::AppHandler>::suspended(unsafe { get_app() });
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__resumed))]
+        pub extern "C" fn __resumed() {
+            // Don't simulate the time we spent in the background
+            unsafe { CLOCK.get_mut() }.reset();
+            <$ty as $crate::AppHandler>::resumed(unsafe { get_app() });
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__before_unload))]
+        pub extern "C" fn __before_unload() {
+            <$ty as $crate::AppHandler>::before_unload(unsafe { get_app() });
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__key_down))]
+        pub extern "C" fn __key_down(code: u32) {
+            if let Some(key) = $crate::KeyCode::from_raw(code) {
+                <$ty as $crate::AppHandler>::key_down(unsafe { get_app() }, key);
+            }
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__key_up))]
+        pub extern "C" fn __key_up(code: u32) {
+            if let Some(key) = $crate::KeyCode::from_raw(code) {
+                <$ty as $crate::AppHandler>::key_up(unsafe { get_app() }, key);
+            }
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__pointer_move))]
+        pub extern "C" fn __pointer_move(x: f32, y: f32) {
+            <$ty as $crate::AppHandler>::pointer_move(unsafe { get_app() }, x, y);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__pointer_down))]
+        pub extern "C" fn __pointer_down(button: u32, x: f32, y: f32) {
+            if let Some(button) = $crate::MouseButton::from_raw(button) {
+                <$ty as $crate::AppHandler>::pointer_down(unsafe { get_app() }, button, x, y);
+            }
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__pointer_up))]
+        pub extern "C" fn __pointer_up(button: u32, x: f32, y: f32) {
+            if let Some(button) = $crate::MouseButton::from_raw(button) {
+                <$ty as $crate::AppHandler>::pointer_up(unsafe { get_app() }, button, x, y);
+            }
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__wheel))]
+        pub extern "C" fn __wheel(dx: f32, dy: f32) {
+            <$ty as $crate::AppHandler>::wheel(unsafe { get_app() }, dx, dy);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__touch_start))]
+        pub extern "C" fn __touch_start(id: u32, x: f32, y: f32) {
+            $crate::dispatch_touch(unsafe { get_app() }, $crate::TouchPhase::Start, id, x, y);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__touch_move))]
+        pub extern "C" fn __touch_move(id: u32, x: f32, y: f32) {
+            $crate::dispatch_touch(unsafe { get_app() }, $crate::TouchPhase::Move, id, x, y);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__touch_end))]
+        pub extern "C" fn __touch_end(id: u32, x: f32, y: f32) {
+            $crate::dispatch_touch(unsafe { get_app() }, $crate::TouchPhase::End, id, x, y);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__touch_cancel))]
+        pub extern "C" fn __touch_cancel(id: u32, x: f32, y: f32) {
+            $crate::dispatch_touch(unsafe { get_app() }, $crate::TouchPhase::Cancel, id, x, y);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__alloc))]
+        pub extern "C" fn __alloc(size: usize, align: usize) -> *mut u8 {
+            $crate::host_alloc(size, align)
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__callback))]
+        pub extern "C" fn __callback(id: u32, arg: u32) {
+            $crate::dispatch_callback(unsafe { get_app() }, id, arg);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__timer))]
+        pub extern "C" fn __timer(id: u32) {
+            $crate::dispatch_timer(unsafe { get_app() }, id);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__gamepads))]
+        pub extern "C" fn __gamepads() -> *mut $crate::GamepadState {
+            $crate::gamepads_ptr()
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__gamepad_connected))]
+        pub extern "C" fn __gamepad_connected(index: u32) {
+            $crate::dispatch_gamepad(unsafe { get_app() }, index, true);
+        }
+
+        #[unsafe(export_name = $crate::wasm_name!(__gamepad_disconnected))]
+        pub extern "C" fn __gamepad_disconnected(index: u32) {
+            $crate::dispatch_gamepad(unsafe { get_app() }, index, false);
         }
     };
 }
\ No newline at end of file
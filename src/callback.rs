@@ -0,0 +1,248 @@
+//! Rust callbacks that JS can call back into, for anything that completes later (fetches, DOM
+//! events and so on).
+//!
+//! Timers keep their callbacks in their own slots (see [crate::set_timeout]), so that
+//! [unregister_callback] can't pull them out from under a pending timer.
+//!
+//! Registering a callback gives a numeric [CallbackId], which is handed over to JS. JS then calls
+//! the exported `__callback(id, arg)`, which finds the callback and runs it with the app.
+
+use core::any::TypeId;
+
+use crate::{ConstVec, StaticCell, alloc_value};
+
+/// The maximum amount of callbacks registered at the same time
+pub const MAX_CALLBACKS: usize = 64;
+
+/// The identifier JS refers to a callback with
+pub type CallbackId = u32;
+
+/// A type-erased callback. `data` is either a plain function pointer or a pointer to a closure
+#[derive(Clone, Copy)]
+pub(crate) struct Callback {
+    app: TypeId,
+    data: *mut (),
+    call: unsafe fn(data: *mut (), app: *mut (), arg: u32)
+}
+
+impl Callback {
+    pub(crate) fn from_fn<A: 'static>(f: fn(&mut A, u32)) -> Self {
+        Self {
+            app: TypeId::of::<A>(),
+            data: f as *mut (),
+            call: call_fn::<A>
+        }
+    }
+
+    /// Run the callback, unless it was registered for a different app type
+    pub(crate) fn dispatch<A: 'static>(self, app: &mut A, arg: u32) {
+        if self.app == TypeId::of::<A>() {
+            unsafe { self.run(app, arg) };
+        }
+    }
+
+    /// # Safety
+    /// `A` must be the app type the callback was registered with
+    unsafe fn run<A>(self, app: &mut A, arg: u32) {
+        unsafe { (self.call)(self.data, app as *mut A as *mut (), arg) };
+    }
+}
+
+unsafe fn call_fn<A>(data: *mut (), app: *mut (), arg: u32) {
+    let f: fn(&mut A, u32) = unsafe { core::mem::transmute(data) };
+
+    f(unsafe { &mut *(app as *mut A) }, arg);
+}
+
+unsafe fn call_closure<A, F: FnMut(&mut A, u32)>(data: *mut (), app: *mut (), arg: u32) {
+    let f = unsafe { &mut *(data as *mut F) };
+
+    f(unsafe { &mut *(app as *mut A) }, arg);
+}
+
+/// A fixed-capacity table of callbacks. Slots of unregistered callbacks are reused
+pub struct Callbacks {
+    slots: ConstVec<Option<Callback>, MAX_CALLBACKS>
+}
+
+impl Callbacks {
+    pub const fn new() -> Self {
+        Self {
+            slots: ConstVec::new()
+        }
+    }
+
+    fn insert(&mut self, callback: Callback) -> Option<CallbackId> {
+        let free = self.slots.as_slice().iter().position(Option::is_none);
+
+        let index = match free {
+            Some(index) => {
+                self.slots.set(index, Some(callback));
+                index
+            },
+            None if !self.slots.is_full() => {
+                self.slots.push(Some(callback));
+                self.slots.len() - 1
+            },
+            None => return None
+        };
+
+        Some(index as CallbackId)
+    }
+
+    /// Register a function. Returns [None] if the table is full
+    pub fn register<A: 'static>(&mut self, f: fn(&mut A, u32)) -> Option<CallbackId> {
+        self.insert(Callback::from_fn(f))
+    }
+
+    /// Register a closure. Returns [None] if the table is full.
+    ///
+    /// Capturing closures are moved into the bump heap, so their memory is never reclaimed,
+    /// even after [Callbacks::unregister]. Register them once and reuse their ids
+    pub fn register_closure<A, F>(&mut self, f: F) -> Option<CallbackId>
+    where
+        A: 'static,
+        F: FnMut(&mut A, u32) + 'static
+    {
+        if self.slots.is_full() && !self.slots.as_slice().iter().any(Option::is_none) {
+            return None;
+        }
+
+        let data = if size_of::<F>() == 0 {
+            // Nothing to store, but the closure still must not be dropped
+            core::mem::forget(f);
+            core::ptr::NonNull::<F>::dangling().as_ptr()
+        } else {
+            alloc_value(f) as *mut F
+        };
+
+        self.insert(Callback {
+            app: TypeId::of::<A>(),
+            data: data as *mut (),
+            call: call_closure::<A, F>
+        })
+    }
+
+    /// Remove a callback, so that its id can be reused
+    pub fn unregister(&mut self, id: CallbackId) {
+        self.slots.set(id as usize, None);
+    }
+
+    fn find<A: 'static>(&self, id: CallbackId) -> Option<Callback> {
+        let callback = (*self.slots.get(id as usize)?)?;
+
+        (callback.app == TypeId::of::<A>()).then_some(callback)
+    }
+
+    /// Call a callback. Unknown ids and callbacks registered for a different app are ignored
+    pub fn dispatch<A: 'static>(&self, app: &mut A, id: CallbackId, arg: u32) {
+        if let Some(callback) = self.find::<A>(id) {
+            unsafe { callback.run(app, arg) };
+        }
+    }
+}
+
+impl Default for Callbacks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static CALLBACKS: StaticCell<Callbacks> = StaticCell::new(Callbacks::new());
+
+/// Register a function JS can call back with `__callback(id, arg)`
+pub fn register_callback<A: 'static>(f: fn(&mut A, u32)) -> Option<CallbackId> {
+    unsafe { CALLBACKS.get_mut() }.register(f)
+}
+
+/// Register a closure JS can call back with `__callback(id, arg)`. See [Callbacks::register_closure]
+pub fn register_closure<A, F>(f: F) -> Option<CallbackId>
+where
+    A: 'static,
+    F: FnMut(&mut A, u32) + 'static
+{
+    unsafe { CALLBACKS.get_mut() }.register_closure(f)
+}
+
+pub fn unregister_callback(id: CallbackId) {
+    unsafe { CALLBACKS.get_mut() }.unregister(id);
+}
+
+/// Forward a `__callback` call from JS to the registered callback
+pub fn dispatch_callback<A: 'static>(app: &mut A, id: CallbackId, arg: u32) {
+    // The callback is copied out first, so that it can register or unregister callbacks itself
+    if let Some(callback) = unsafe { CALLBACKS.get() }.find::<A>(id) {
+        unsafe { callback.run(app, arg) };
+    }
+}
+
+/// Like [dispatch_callback], but unregisters the callback right before running it, for things
+/// that only ever complete once
+pub fn dispatch_callback_once<A: 'static>(app: &mut A, id: CallbackId, arg: u32) {
+    if let Some(callback) = unsafe { CALLBACKS.get() }.find::<A>(id) {
+        unregister_callback(id);
+        unsafe { callback.run(app, arg) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        total: u32
+    }
+
+    fn add(app: &mut Counter, arg: u32) {
+        app.total += arg;
+    }
+
+    #[test]
+    fn test_callbacks() {
+        let mut callbacks = Callbacks::new();
+        let mut app = Counter::default();
+
+        let add_id = callbacks.register(add).unwrap();
+        let double_id = callbacks.register_closure(|app: &mut Counter, _| app.total *= 2).unwrap();
+        assert_ne!(add_id, double_id);
+
+        // Capturing closures live in the heap
+        let mut calls = 0;
+        let count_id = callbacks.register_closure(move |app: &mut Counter, _| {
+            calls += 100;
+            app.total += calls;
+        }).unwrap();
+
+        callbacks.dispatch(&mut app, add_id, 5);
+        callbacks.dispatch(&mut app, double_id, 0);
+        assert_eq!(app.total, 10);
+
+        callbacks.dispatch(&mut app, count_id, 0);
+        callbacks.dispatch(&mut app, count_id, 0);
+        assert_eq!(app.total, 310);
+        app.total = 10;
+
+        // Unknown ids and other app types are ignored
+        callbacks.dispatch(&mut app, 42, 1);
+        callbacks.dispatch(&mut 0u32, add_id, 1);
+        assert_eq!(app.total, 10);
+
+        // Slots are reused
+        callbacks.unregister(add_id);
+        callbacks.dispatch(&mut app, add_id, 1);
+        assert_eq!(callbacks.register(add), Some(add_id));
+    }
+
+    #[test]
+    fn test_callbacks_capacity() {
+        let mut callbacks = Callbacks::new();
+
+        for _ in 0..MAX_CALLBACKS {
+            assert!(callbacks.register(add).is_some());
+        }
+
+        assert_eq!(callbacks.register(add), None);
+        assert_eq!(callbacks.register_closure(|_: &mut Counter, _| ()), None);
+    }
+}
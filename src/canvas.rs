@@ -0,0 +1,52 @@
+//! The size of the canvas, kept in sync by the JS side
+
+use crate::{AppHandler, StaticCell, glViewport};
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    scale: f32,
+
+    /// Whether the GL viewport has to be updated before the next draw
+    dirty: bool
+}
+
+static CANVAS: StaticCell<Canvas> = StaticCell::new(Canvas {
+    width: 0,
+    height: 0,
+    scale: 1.0,
+    dirty: false
+});
+
+/// The size of the canvas' backing store in pixels
+pub fn canvas_size() -> (u32, u32) {
+    let canvas = unsafe { CANVAS.get() };
+    (canvas.width, canvas.height)
+}
+
+/// The amount of canvas pixels per CSS pixel (`devicePixelRatio`)
+pub fn canvas_scale() -> f32 {
+    unsafe { CANVAS.get() }.scale
+}
+
+/// Store the new canvas size and notify the app
+pub fn dispatch_resize<A: AppHandler>(app: &mut A, width: u32, height: u32, scale: f32) {
+    let canvas = unsafe { CANVAS.get_mut() };
+    canvas.width = width;
+    canvas.height = height;
+    canvas.scale = scale;
+    canvas.dirty = true;
+
+    app.resize(width, height, scale);
+}
+
+/// Update the GL viewport if the canvas was resized since the last call
+pub fn apply_viewport() {
+    let canvas = unsafe { CANVAS.get_mut() };
+
+    if canvas.dirty {
+        canvas.dirty = false;
+
+        unsafe { glViewport(0, 0, canvas.width as _, canvas.height as _) };
+    }
+}
@@ -25,15 +25,49 @@ impl<T> InitCell<T> {
         unsafe { (&*self.0.get()).as_ref() }
     } 
 
+    /// # Safety
+    /// The cell must be initialized
     pub unsafe fn get_unchecked(&self) -> &T {
         unsafe { self.get().unwrap_unchecked() }
     }
 }
 
+impl<T> Default for InitCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Wasm has only one thread
 unsafe impl<T> Sync for InitCell<T> {}
 unsafe impl<T> Send for InitCell<T> {}
 
+/// A mutable static, initialized at compile time
+pub struct StaticCell<T>(UnsafeCell<T>);
+
+impl<T> StaticCell<T> {
+    pub const fn new(value: T) -> Self {
+        Self(UnsafeCell::new(value))
+    }
+
+    /// # Safety
+    /// The value can't be mutated (through [StaticCell::get_mut]) while the returned reference
+    /// is used
+    pub unsafe fn get(&self) -> &T {
+        unsafe { &*self.0.get() }
+    }
+
+    /// # Safety
+    /// No other reference to the value can be alive while the returned one is used
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self) -> &mut T {
+        unsafe { &mut *self.0.get() }
+    }
+}
+
+// Wasm has only one thread
+unsafe impl<T> Sync for StaticCell<T> {}
+unsafe impl<T> Send for StaticCell<T> {}
 
 enum InitOrSome<T, F>
 where F: FnOnce() -> T {
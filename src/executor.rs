@@ -0,0 +1,384 @@
+//! A tiny single-threaded executor, so that asset loads and storage reads can be `await`ed.
+//! Enabled with the `executor` feature.
+//!
+//! Spawned futures are moved into fixed-size task slots, which are allocated from the bump heap
+//! on the first spawn and reused afterwards. Woken tasks are polled once per frame, including
+//! the ones waiting on promises JS settled since (see [JsFuture]).
+//! ```ignore
+//! spawn(async {
+//!     sleep(500).await;
+//!     log_info!("half a second later");
+//! });
+//! ```
+
+use core::{
+    cell::Cell,
+    mem::MaybeUninit,
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker}
+};
+
+use crate::{ID_GENERATION_MASK, ID_INDEX_BITS, ID_INDEX_MASK, StaticCell, alloc, layouts_of};
+
+/// The maximum amount of tasks alive at the same time
+pub const MAX_TASKS: usize = 16;
+
+/// The maximum size of a spawned future
+pub const TASK_SIZE: usize = 256;
+
+#[repr(C, align(16))]
+struct TaskSlot(MaybeUninit<[u8; TASK_SIZE]>);
+
+/// A type-erased future living in a task slot
+#[derive(Clone, Copy)]
+struct Task {
+    poll: unsafe fn(*mut (), &mut Context) -> Poll<()>,
+    drop: unsafe fn(*mut ())
+}
+
+unsafe fn poll_task<F: Future<Output = ()>>(data: *mut (), cx: &mut Context) -> Poll<()> {
+    // Task slots never move, so the future is pinned
+    unsafe { Pin::new_unchecked(&mut *(data as *mut F)) }.poll(cx)
+}
+
+unsafe fn drop_task<F>(data: *mut ()) {
+    unsafe { (data as *mut F).drop_in_place() };
+}
+
+// Our wakers point straight at the `scheduled` flag of their task
+const WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(
+    |flag| RawWaker::new(flag, &WAKER_VTABLE),
+    wake,
+    wake,
+    |_| ()
+);
+
+fn wake(flag: *const ()) {
+    unsafe { &*(flag as *const Cell<bool>) }.set(true);
+}
+
+/// The executor itself. Everything is behind cells, so that tasks can spawn other tasks while
+/// they're being polled.
+///
+/// Wakers point into the executor, so spawning and running take `&'static self`: an executor
+/// with tasks can't move, and stays alive as long as anything can wake them
+pub struct Executor {
+    slots: Cell<*mut TaskSlot>,
+    tasks: [Cell<Option<Task>>; MAX_TASKS],
+    scheduled: [Cell<bool>; MAX_TASKS]
+}
+
+impl Executor {
+    pub const fn new() -> Self {
+        Self {
+            slots: Cell::new(core::ptr::null_mut()),
+            tasks: [const { Cell::new(None) }; MAX_TASKS],
+            scheduled: [const { Cell::new(false) }; MAX_TASKS]
+        }
+    }
+
+    fn slot(&self, index: usize) -> *mut () {
+        if self.slots.get().is_null() {
+            self.slots.set(unsafe { alloc(layouts_of::<TaskSlot>(MAX_TASKS)) } as *mut TaskSlot);
+        }
+
+        unsafe { self.slots.get().add(index) as *mut () }
+    }
+
+    /// Spawn a future, which is first polled on the next [Executor::run]. Returns `false` if
+    /// all the task slots are taken.
+    ///
+    /// Futures bigger than [TASK_SIZE] fail to compile
+    pub fn spawn<F: Future<Output = ()> + 'static>(&'static self, future: F) -> bool {
+        const {
+            assert!(
+                size_of::<F>() <= TASK_SIZE && align_of::<F>() <= align_of::<TaskSlot>(),
+                "The future doesn't fit into a task slot"
+            );
+        }
+
+        let Some(index) = self.tasks.iter().position(|task| task.get().is_none()) else {
+            return false;
+        };
+
+        unsafe { (self.slot(index) as *mut F).write(future) };
+
+        self.tasks[index].set(Some(Task {
+            poll: poll_task::<F>,
+            drop: drop_task::<F>
+        }));
+        self.scheduled[index].set(true);
+
+        true
+    }
+
+    /// The amount of tasks that haven't finished yet
+    pub fn len(&self) -> usize {
+        self.tasks.iter().filter(|task| task.get().is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Poll every task that was woken since the last run. Tasks woken while polling (or spawned)
+    /// might have to wait for the next run
+    pub fn run(&'static self) {
+        for index in 0..MAX_TASKS {
+            if !self.scheduled[index].replace(false) {
+                continue;
+            }
+
+            let Some(task) = self.tasks[index].get() else {
+                continue;
+            };
+
+            let flag = &self.scheduled[index] as *const Cell<bool> as *const ();
+            let waker = unsafe { Waker::from_raw(RawWaker::new(flag, &WAKER_VTABLE)) };
+
+            let data = self.slot(index);
+            if unsafe { (task.poll)(data, &mut Context::from_waker(&waker)) }.is_ready() {
+                unsafe { (task.drop)(data) };
+                self.tasks[index].set(None);
+            }
+        }
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        // Dropping the futures also frees the promises they were waiting on
+        for (index, task) in self.tasks.iter().enumerate() {
+            if let Some(task) = task.take() {
+                unsafe { (task.drop)(self.slots.get().add(index) as *mut ()) };
+            }
+        }
+    }
+}
+
+// Wasm has only one thread
+unsafe impl Sync for Executor {}
+unsafe impl Send for Executor {}
+
+static EXECUTOR: Executor = Executor::new();
+
+/// Spawn a task on the global executor. See [Executor::spawn]
+pub fn spawn<F: Future<Output = ()> + 'static>(future: F) -> bool {
+    EXECUTOR.spawn(future)
+}
+
+/// Poll the woken tasks of the global executor. Called by [crate::make_app] on every frame
+pub fn run_tasks() {
+    EXECUTOR.run();
+}
+
+/// A future that's pending until the next run of the executor (usually the next frame)
+pub struct NextFrame(bool);
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
+
+/// Wait until the next frame
+pub fn next_frame() -> NextFrame {
+    NextFrame(false)
+}
+
+/// The maximum amount of promises awaited at the same time
+pub const MAX_PROMISES: usize = 16;
+
+/// The identifier JS settles a promise with. The upper bits hold a generation, so that late
+/// settlements of dropped futures don't leak into new ones
+pub type PromiseId = u32;
+
+enum PromiseState {
+    Free,
+    Pending(Option<Waker>),
+    Ready(Option<u32>)
+}
+
+struct Promise {
+    generation: u32,
+    state: PromiseState
+}
+
+static PROMISES: StaticCell<[Promise; MAX_PROMISES]> = StaticCell::new(
+    [const { Promise { generation: 0, state: PromiseState::Free } }; MAX_PROMISES]
+);
+
+fn promise(id: PromiseId) -> Option<&'static mut Promise> {
+    let promise = unsafe { PROMISES.get_mut() }.get_mut((id & ID_INDEX_MASK) as usize)?;
+
+    (promise.generation == id >> ID_INDEX_BITS).then_some(promise)
+}
+
+/// A value JS hands over later, by settling a promise through the exported `__resolve`.
+///
+/// It resolves to [None] if the JS promise was rejected
+pub struct JsFuture {
+    id: PromiseId
+}
+
+impl JsFuture {
+    /// Reserve a promise. Returns [None] if [MAX_PROMISES] are already pending
+    pub fn new() -> Option<Self> {
+        let promises = unsafe { PROMISES.get_mut() };
+        let index = promises.iter().position(|promise| matches!(promise.state, PromiseState::Free))?;
+
+        let promise = &mut promises[index];
+        promise.state = PromiseState::Pending(None);
+
+        Some(Self {
+            id: promise.generation << ID_INDEX_BITS | index as u32
+        })
+    }
+
+    /// The id to hand over to JS
+    pub fn id(&self) -> PromiseId {
+        self.id
+    }
+}
+
+impl Future for JsFuture {
+    type Output = Option<u32>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        let Some(promise) = promise(self.id) else {
+            return Poll::Ready(None);
+        };
+
+        match &mut promise.state {
+            PromiseState::Ready(value) => Poll::Ready(*value),
+            PromiseState::Pending(waker) => {
+                *waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+            PromiseState::Free => Poll::Ready(None)
+        }
+    }
+}
+
+impl Drop for JsFuture {
+    fn drop(&mut self) {
+        if let Some(promise) = promise(self.id) {
+            promise.state = PromiseState::Free;
+            promise.generation = (promise.generation + 1) & ID_GENERATION_MASK;
+        }
+    }
+}
+
+/// Settle a promise from JS and wake the task waiting on it. Unknown or stale ids are ignored
+pub fn resolve_promise(id: PromiseId, value: Option<u32>) {
+    let Some(promise) = promise(id) else {
+        return;
+    };
+
+    if let PromiseState::Pending(waker) = &mut promise.state {
+        if let Some(waker) = waker.take() {
+            waker.wake();
+        }
+
+        promise.state = PromiseState::Ready(value);
+    }
+}
+
+/// Settle a promise from JS. Exported here rather than by [crate::make_app], so that it only
+/// exists with the `executor` feature. The task waiting on it runs on the next frame
+#[unsafe(export_name = crate::wasm_name!(__resolve))]
+pub extern "C" fn __resolve(id: PromiseId, ok: u32, value: u32) {
+    resolve_promise(id, (ok != 0).then_some(value));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{boxed::Box, rc::Rc};
+
+    #[test]
+    fn test_executor() {
+        static EXECUTOR: Executor = Executor::new();
+        let executor = &EXECUTOR;
+
+        let loaded = JsFuture::new().unwrap();
+        let id = loaded.id();
+
+        let result = Rc::new(Cell::new(None));
+        let frames = Rc::new(Cell::new(0));
+
+        let (task_result, task_frames) = (result.clone(), frames.clone());
+        assert!(executor.spawn(async move {
+            task_result.set(Some(loaded.await));
+
+            for _ in 0..2 {
+                next_frame().await;
+                task_frames.set(task_frames.get() + 1);
+            }
+        }));
+
+        // Nothing happens until the promise is settled
+        executor.run();
+        executor.run();
+        assert_eq!(result.get(), None);
+
+        resolve_promise(id, Some(42));
+        executor.run();
+        assert_eq!(result.get(), Some(Some(42)));
+
+        executor.run();
+        executor.run();
+        assert_eq!(frames.get(), 2);
+        assert!(executor.is_empty());
+
+        // The promise was freed along with the task, so settling it again does nothing
+        resolve_promise(id, Some(7));
+        let next = JsFuture::new().unwrap();
+        assert_ne!(next.id(), id);
+    }
+
+    #[test]
+    fn test_executor_capacity() {
+        static EXECUTOR: Executor = Executor::new();
+        let executor = &EXECUTOR;
+
+        for _ in 0..MAX_TASKS {
+            assert!(executor.spawn(core::future::pending()));
+        }
+
+        assert!(!executor.spawn(async {}));
+        assert_eq!(executor.len(), MAX_TASKS);
+    }
+
+    #[test]
+    fn test_executor_drop() {
+        let executor: &'static Executor = Box::leak(Box::new(Executor::new()));
+
+        let loaded = JsFuture::new().unwrap();
+        let id = loaded.id();
+
+        assert!(executor.spawn(async move {
+            loaded.await;
+        }));
+        executor.run();
+
+        // The waiting task is dropped along with the executor, and its promise with it
+        drop(unsafe { Box::from_raw(executor as *const Executor as *mut Executor) });
+        assert!(promise(id).is_none());
+    }
+}
@@ -0,0 +1,259 @@
+//! Minimal string formatting. `core::fmt` is notoriously heavy, so by default we only ever
+//! push whole strings into fixed stack buffers.
+
+/// A string stored in a fixed buffer on the stack. Anything that doesn't fit is truncated
+pub struct StackStr<const N: usize> {
+    buf: [u8; N],
+    len: usize
+}
+
+impl<const N: usize> StackStr<N> {
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_str(&self) -> &str {
+        // We only ever push whole characters, so this is always valid UTF-8. Bounds checks
+        // are avoided here and below, since they'd link in the panic formatting we're avoiding
+        unsafe { str::from_utf8_unchecked(self.buf.get_unchecked(..self.len)) }
+    }
+
+    /// Push a string, truncating it at a character boundary if it doesn't fit.
+    ///
+    /// Returns `false` if the string was truncated
+    pub fn push_str(&mut self, s: &str) -> bool {
+        let mut end = s.len().min(N - self.len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(s.as_ptr(), self.buf.as_mut_ptr().add(self.len), end)
+        };
+        self.len += end;
+
+        end == s.len()
+    }
+
+    pub fn push_u64(&mut self, mut value: u64) {
+        // `u64::MAX` has 20 digits
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+
+        loop {
+            start -= 1;
+            unsafe { *digits.get_unchecked_mut(start) = b'0' + (value % 10) as u8 };
+            value /= 10;
+
+            if value == 0 {
+                break;
+            }
+        }
+
+        self.push_str(unsafe { str::from_utf8_unchecked(digits.get_unchecked(start..)) });
+    }
+
+    pub fn push_i64(&mut self, value: i64) {
+        if value < 0 {
+            self.push_str("-");
+        }
+
+        self.push_u64(value.unsigned_abs());
+    }
+
+    /// Push a float with a fixed amount of decimals (at most 9)
+    pub fn push_f64(&mut self, value: f64, decimals: u32) {
+        if value.is_nan() {
+            self.push_str("NaN");
+            return;
+        }
+
+        if value.is_sign_negative() {
+            self.push_str("-");
+        }
+
+        let value = value.abs();
+        if value.is_infinite() {
+            self.push_str("inf");
+            return;
+        }
+
+        // Round once on the scaled value, so that carries propagate into the integer part.
+        // Values that don't fit simply saturate
+        let scale = 10u64.pow(decimals.min(9));
+        let scaled = (value * scale as f64 + 0.5) as u64;
+
+        self.push_u64(scaled / scale);
+
+        if decimals > 0 {
+            self.push_str(".");
+
+            // Push the fraction digit by digit to keep the leading zeros
+            let fraction = scaled % scale;
+            let mut digit = scale / 10;
+            while digit > 0 {
+                let chr = [b'0' + (fraction / digit % 10) as u8];
+                self.push_str(unsafe { str::from_utf8_unchecked(&chr) });
+                digit /= 10;
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for StackStr<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Write for StackStr<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+/// Values that can be pushed into a [StackStr] without `core::fmt`
+pub trait Render {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>);
+}
+
+impl<T: Render + ?Sized> Render for &T {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+        (**self).render(out);
+    }
+}
+
+impl Render for str {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+        out.push_str(self);
+    }
+}
+
+impl Render for bool {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+        out.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+impl Render for char {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+        out.push_str(self.encode_utf8(&mut [0; 4]));
+    }
+}
+
+/// The amount of decimals floats are rendered with
+pub const FLOAT_DECIMALS: u32 = 3;
+
+impl Render for f32 {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+        out.push_f64(*self as f64, FLOAT_DECIMALS);
+    }
+}
+
+impl Render for f64 {
+    fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+        out.push_f64(*self, FLOAT_DECIMALS);
+    }
+}
+
+macro_rules! impl_render_int {
+    ($push:ident as $cast:ty: $($ty:ty),+) => {
+        $(
+            impl Render for $ty {
+                fn render<const N: usize>(&self, out: &mut StackStr<N>) {
+                    out.$push(*self as $cast);
+                }
+            }
+        )+
+    };
+}
+
+impl_render_int!(push_u64 as u64: u8, u16, u32, u64, usize);
+impl_render_int!(push_i64 as i64: i8, i16, i32, i64, isize);
+
+/// The size of the buffer panic messages are rendered into
+pub const PANIC_MESSAGE_SIZE: usize = 256;
+
+/// Render a panic message into a stack buffer.
+///
+/// Without the `panic-fmt` feature only static messages (like `panic!("oops")`) are rendered,
+/// since anything with arguments needs `core::fmt`.
+pub fn write_panic_message<const N: usize>(
+    out: &mut StackStr<N>,
+    message: core::panic::PanicMessage
+) {
+    #[cfg(feature = "panic-fmt")]
+    {
+        let _ = core::fmt::Write::write_fmt(out, format_args!("{message}"));
+    }
+
+    #[cfg(not(feature = "panic-fmt"))]
+    {
+        out.push_str(
+            message.as_str()
+                .unwrap_or("<formatted message, enable the `panic-fmt` feature to see it>")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_str() {
+        let mut s: StackStr<8> = StackStr::new();
+
+        assert!(s.push_str("abc"));
+        assert!(s.push_str("def"));
+        assert_eq!(s.as_str(), "abcdef");
+
+        // Only 2 bytes are left, and `ö` takes 2 of them
+        assert!(!s.push_str("öx"));
+        assert_eq!(s.as_str(), "abcdefö");
+        assert_eq!(s.len(), 8);
+
+        let mut s: StackStr<3> = StackStr::new();
+
+        // A multi-byte character is never split
+        assert!(!s.push_str("a€"));
+        assert_eq!(s.as_str(), "a");
+    }
+
+    fn render<T: Render>(value: T) -> StackStr<32> {
+        let mut s = StackStr::new();
+        value.render(&mut s);
+        s
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(0u32).as_str(), "0");
+        assert_eq!(render(u64::MAX).as_str(), "18446744073709551615");
+        assert_eq!(render(i64::MIN).as_str(), "-9223372036854775808");
+        assert_eq!(render(-42i8).as_str(), "-42");
+
+        assert_eq!(render(1.5f32).as_str(), "1.500");
+        assert_eq!(render(-0.0625f64).as_str(), "-0.063");
+        assert_eq!(render(0.0009f64).as_str(), "0.001");
+        assert_eq!(render(9.9996f64).as_str(), "10.000");
+        assert_eq!(render(f32::NAN).as_str(), "NaN");
+        assert_eq!(render(f64::NEG_INFINITY).as_str(), "-inf");
+
+        assert_eq!(render("hi").as_str(), "hi");
+        assert_eq!(render(true).as_str(), "true");
+        assert_eq!(render('ö').as_str(), "ö");
+    }
+}
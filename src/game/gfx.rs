@@ -1,6 +1,6 @@
 use core::{ffi::CStr, ptr::null};
 
-use crate::gl::*;
+use crate::{gl::*, log_error};
 
 enum BufferKind {
     Vertex,
@@ -129,10 +129,12 @@ fn make_shader(src: &CStr, kind: ShaderKind) -> Shader {
         }
 
         let chr_slice = &message[0..(message_len) as usize];
-        let s = str::from_utf8(chr_slice).unwrap();
-        
-        // For now we're going to panic
-        panic!("{s}");
+        let s = str::from_utf8(chr_slice).unwrap_or("<invalid UTF-8>");
+
+        // The log is only known at runtime, so it can't be part of the panic message without the
+        // `panic-fmt` feature. For now we're going to panic
+        log_error!("Failed to compile a shader: ", s);
+        panic!("Failed to compile a shader");
     }
 
     Shader {
@@ -1,12 +1,16 @@
 use crate::AppHandler;
 
+// Nothing draws through these yet
+#[allow(dead_code)]
 mod gfx;
 
+#[allow(dead_code)]
 enum GameState {
     Menu,
     InGame
 }
 
+#[allow(dead_code)]
 pub struct Game {
     state: GameState
 }
@@ -16,7 +20,7 @@ impl Game {
 }
 
 impl AppHandler for Game {
-    fn draw(&mut self) {
+    fn draw(&mut self, _alpha: f32) {
         
     }
 }
\ No newline at end of file
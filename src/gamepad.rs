@@ -0,0 +1,114 @@
+//! Gamepad support. The JS side polls `navigator.getGamepads()` once per frame and writes
+//! the state of every connected pad directly into a static buffer, so reading it costs us nothing.
+
+use crate::{AppHandler, StaticCell};
+
+/// The maximum amount of gamepads we keep track of
+pub const MAX_GAMEPADS: usize = 4;
+
+/// The amount of axes per pad (two sticks)
+pub const MAX_AXES: usize = 4;
+
+/// Buttons of the standard gamepad mapping
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    Home,
+}
+
+/// Axes of the standard gamepad mapping
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+}
+
+/// The state of a single pad.
+///
+/// NOTE: The layout must match `pollGamepads` in `web/glue/runtime.js`
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct GamepadState {
+    connected: u32,
+    buttons: u32,
+    axes: [f32; MAX_AXES]
+}
+
+impl GamepadState {
+    const fn new() -> Self {
+        Self {
+            connected: 0,
+            buttons: 0,
+            axes: [0.0; MAX_AXES]
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected != 0
+    }
+
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons & (1 << button as u32) != 0
+    }
+
+    /// Get the value of an axis in the `-1..=1` range
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+}
+
+static GAMEPADS: StaticCell<[GamepadState; MAX_GAMEPADS]> = StaticCell::new(
+    [const { GamepadState::new() }; MAX_GAMEPADS]
+);
+
+/// Get a copy of the state of a connected gamepad. JS overwrites the state on every frame, so
+/// there's nothing to hold a reference to
+pub fn gamepad(index: usize) -> Option<GamepadState> {
+    unsafe { GAMEPADS.get() }
+        .get(index)
+        .copied()
+        .filter(GamepadState::is_connected)
+}
+
+/// The buffer the JS side writes the polled state into
+pub fn gamepads_ptr() -> *mut GamepadState {
+    unsafe { GAMEPADS.get_mut() }.as_mut_ptr()
+}
+
+/// Update the connection state of a pad and notify the app. Pads outside of [MAX_GAMEPADS]
+/// are ignored.
+pub fn dispatch_gamepad<A: AppHandler>(app: &mut A, index: u32, connected: bool) {
+    let Some(pad) = unsafe { GAMEPADS.get_mut() }.get_mut(index as usize) else {
+        return;
+    };
+
+    *pad = GamepadState {
+        connected: connected as u32,
+        ..GamepadState::new()
+    };
+
+    if connected {
+        app.gamepad_connected(index as usize);
+    } else {
+        app.gamepad_disconnected(index as usize);
+    }
+}
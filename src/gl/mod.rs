@@ -1,3 +1,41 @@
+/// Declare the GL entry points. On wasm these are imports from the JS side, while natively
+/// they are stubs recorded by [crate::host].
+macro_rules! gl_functions {
+    ($(pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        #[cfg(target_family = "wasm")]
+        unsafe extern "C" {
+            $(
+                #[link_name = crate::wasm_name!($name)]
+                pub fn $name($($arg: $ty),*) $(-> $ret)?;
+            )*
+        }
+
+        $(
+            #[cfg(not(target_family = "wasm"))]
+            #[allow(unused_variables, clippy::missing_safety_doc, clippy::too_many_arguments)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                crate::host::gl_call(stringify!($name))
+            }
+        )*
+    };
+}
+
 mod webgl;
 
-pub use webgl::*;
\ No newline at end of file
+pub use webgl::*;
+
+/// Read a string from `glGetString` (or `glGetStringi` when `index` is provided), like
+/// [GL_VERSION] or an extension name. JS allocates every string only once, so they live forever.
+pub fn gl_string(name: GLenum, index: Option<GLuint>) -> Option<&'static str> {
+    let ptr = match index {
+        Some(index) => unsafe { glGetStringi(name, index) },
+        None => unsafe { glGetString(name) }
+    };
+
+    if ptr.is_null() {
+        return None;
+    }
+
+    let string = unsafe { core::ffi::CStr::from_ptr(ptr as *const core::ffi::c_char) };
+    string.to_str().ok()
+}
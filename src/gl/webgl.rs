@@ -334,7 +334,7 @@ pub struct __GLsync {
 }
 pub type GLsync = *mut __GLsync;
 
-unsafe extern "C" {
+gl_functions! {
     pub fn glActiveTexture(texture: GLenum);
     pub fn glAttachShader(program: GLuint, shader: GLuint);
     pub fn glBindAttribLocation(program: GLuint, index: GLuint, name: *const GLchar);
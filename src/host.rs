@@ -0,0 +1,420 @@
+//! A native stand-in for the browser, so that the crate can run off-wasm (in `cargo test` and
+//! under miri).
+//!
+//! Every import from `src/js.rs` is implemented here with plain Rust: logging goes to stdout,
+//! memory pages are simulated with a counter over a static buffer, and GL calls are only recorded by name, so tests
+//! can check what an app would have done. Host strings (like `localStorage`) are whatever tests
+//! set with [set_host_string].
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    println,
+    string::String,
+    sync::{Mutex, MutexGuard, PoisonError},
+    thread_local,
+    vec::Vec
+};
+
+use core::{cell::UnsafeCell, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::PAGE_SIZE;
+
+/// The amount of pages our simulated memory starts with
+pub const INITIAL_PAGES: usize = 17;
+
+/// The amount of pages the heap can grow by. Requesting more than that fails
+pub const HEAP_PAGES: usize = 64;
+
+static PAGES: AtomicUsize = AtomicUsize::new(INITIAL_PAGES);
+
+/// The memory the allocator hands out. Unlike wasm memory it can't grow, so it's reserved
+/// up-front, aligned to a page, just like `__heap_base` would be if the heap had no waste
+#[repr(C, align(65536))]
+struct Heap(UnsafeCell<[u8; HEAP_PAGES * PAGE_SIZE]>);
+
+unsafe impl Sync for Heap {}
+
+static HEAP: Heap = Heap(UnsafeCell::new([0; HEAP_PAGES * PAGE_SIZE]));
+
+pub(crate) fn heap_base() -> *const u8 {
+    HEAP.0.get() as *const u8
+}
+
+/// A simulated linear memory of its own, so that allocator tests don't share the heap. The heap
+/// starts `waste` bytes into the memory, as if statics and the stack came before it
+#[cfg(test)]
+pub(crate) struct TestMemory {
+    start: *mut u8,
+    max_pages: usize,
+    used_pages: usize,
+    waste: usize
+}
+
+#[cfg(test)]
+impl TestMemory {
+    /// A memory that can grow up to `max_pages`
+    pub(crate) fn new(waste: usize, max_pages: usize) -> Self {
+        assert!(waste <= max_pages * PAGE_SIZE);
+
+        Self {
+            start: unsafe { std::alloc::alloc_zeroed(Self::layout(max_pages)) },
+            max_pages,
+            used_pages: waste.div_ceil(PAGE_SIZE),
+            waste
+        }
+    }
+
+    fn layout(max_pages: usize) -> core::alloc::Layout {
+        core::alloc::Layout::from_size_align(max_pages.max(1) * PAGE_SIZE, PAGE_SIZE).unwrap()
+    }
+
+    /// The amount of pages the memory grew to
+    pub(crate) fn pages(&self) -> usize {
+        self.used_pages
+    }
+}
+
+#[cfg(test)]
+impl crate::MemorySource for TestMemory {
+    fn heap_base(&self) -> *const u8 {
+        unsafe { self.start.add(self.waste) }
+    }
+
+    fn request_pages(&mut self, pages: usize) -> Option<usize> {
+        if self.used_pages + pages > self.max_pages {
+            return None;
+        }
+
+        self.used_pages += pages;
+        Some(self.used_pages - pages)
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestMemory {
+    fn drop(&mut self) {
+        unsafe { std::alloc::dealloc(self.start, Self::layout(self.max_pages)) };
+    }
+}
+
+static HEAP_LOCK: Mutex<()> = Mutex::new(());
+
+/// The allocator itself isn't thread-safe, so allocations are serialized off-wasm
+pub(crate) fn lock_heap() -> MutexGuard<'static, ()> {
+    HEAP_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+thread_local! {
+    static GL_CALLS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static TIMERS: RefCell<Vec<Timer>> = const { RefCell::new(Vec::new()) };
+    static HOST_STRINGS: RefCell<BTreeMap<(u32, String), String>> = const {
+        RefCell::new(BTreeMap::new())
+    };
+}
+
+/// A pending timer. Nothing fires them natively, tests call [crate::dispatch_timer] instead
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Timer {
+    pub id: u32,
+    pub ms: u32,
+    pub repeat: bool
+}
+
+/// Get the timers that are pending on this thread
+pub fn timers() -> Vec<Timer> {
+    TIMERS.with_borrow(|timers| timers.clone())
+}
+
+/// Set a string JS would return on this thread. `None` removes it
+pub fn set_host_string(source: crate::HostString, key: &str, value: Option<&str>) {
+    HOST_STRINGS.with_borrow_mut(|strings| {
+        let key = (source as u32, String::from(key));
+        match value {
+            Some(value) => strings.insert(key, String::from(value)),
+            None => strings.remove(&key)
+        };
+    });
+}
+
+/// Get the names of all GL functions called on this thread, in order
+pub fn gl_calls() -> Vec<&'static str> {
+    GL_CALLS.with_borrow(|calls| calls.clone())
+}
+
+/// Forget all the recorded GL calls of this thread
+pub fn clear_gl_calls() {
+    GL_CALLS.with_borrow_mut(|calls| calls.clear());
+}
+
+/// The value GL stubs return
+pub(crate) trait HostDefault {
+    fn host_default() -> Self;
+}
+
+macro_rules! impl_host_default {
+    ($($ty:ty),+) => {
+        $(
+            impl HostDefault for $ty {
+                fn host_default() -> Self {
+                    Default::default()
+                }
+            }
+        )+
+    };
+}
+
+impl_host_default!((), u8, i32, u32);
+
+impl<T> HostDefault for *const T {
+    fn host_default() -> Self {
+        core::ptr::null()
+    }
+}
+
+impl<T> HostDefault for *mut T {
+    fn host_default() -> Self {
+        core::ptr::null_mut()
+    }
+}
+
+/// Record a GL call
+pub(crate) fn gl_call<T: HostDefault>(name: &'static str) -> T {
+    GL_CALLS.with_borrow_mut(|calls| calls.push(name));
+
+    T::host_default()
+}
+
+unsafe fn host_str<'a>(start: *const u8, len: usize) -> &'a str {
+    let bytes = unsafe { core::slice::from_raw_parts(start, len) };
+
+    str::from_utf8(bytes).unwrap()
+}
+
+pub(crate) unsafe fn js_request_pages(pages: usize) -> isize {
+    let grown = PAGES.try_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        current.checked_add(pages).filter(|total| total - INITIAL_PAGES <= HEAP_PAGES)
+    });
+
+    // Just like `memory.grow`, which throws past the module's maximum
+    grown.map_or(-1, |previous| previous as isize)
+}
+
+pub(crate) unsafe fn js_allocated_pages() -> usize {
+    PAGES.load(Ordering::Relaxed)
+}
+
+pub(crate) unsafe fn js_println(start: *const u8, len: usize) {
+    println!("{}", unsafe { host_str(start, len) });
+}
+
+pub(crate) unsafe fn js_log(level: u32, start: *const u8, len: usize) {
+    let level = ["ERROR", "WARN", "INFO", "DEBUG"][level as usize - 1];
+
+    println!("[{level}] {}", unsafe { host_str(start, len) });
+}
+
+pub(crate) unsafe fn js_alert(start: *const u8, len: usize) {
+    println!("[ALERT] {}", unsafe { host_str(start, len) });
+}
+
+pub(crate) unsafe fn js_panic(
+    err: *const u8, err_len: usize,
+    file: *const u8, file_len: usize,
+    line: u32
+) {
+    let mut message = String::new();
+    if err_len > 0 {
+        message.push_str(unsafe { host_str(err, err_len) });
+    }
+
+    let file = unsafe { host_str(file, file_len) };
+
+    panic!("Caught a panic in {file} at line {line} :\n{message}");
+}
+
+pub(crate) unsafe fn js_println_number(number: usize) {
+    println!("{number}");
+}
+
+pub(crate) unsafe fn js_read_string(
+    source: u32,
+    key: *const u8, key_len: usize,
+    dst: *mut u8, capacity: usize
+) -> isize {
+    let key = (source, String::from(unsafe { host_str(key, key_len) }));
+
+    HOST_STRINGS.with_borrow(|strings| {
+        let Some(value) = strings.get(&key) else {
+            return -1;
+        };
+
+        // Just like JS, only write strings that fit whole
+        if value.len() <= capacity {
+            unsafe { core::ptr::copy_nonoverlapping(value.as_ptr(), dst, value.len()) };
+        }
+
+        value.len() as isize
+    })
+}
+
+pub(crate) unsafe fn js_set_timer(id: u32, ms: u32, repeat: u32) {
+    TIMERS.with_borrow_mut(|timers| timers.push(Timer { id, ms, repeat: repeat != 0 }));
+}
+
+pub(crate) unsafe fn js_clear_timer(id: u32) {
+    TIMERS.with_borrow_mut(|timers| timers.retain(|timer| timer.id != id));
+}
+
+#[cfg(feature = "executor")]
+pub(crate) unsafe fn js_sleep(promise: u32, _ms: u32) {
+    // There's no event loop to wait on, so sleeps are over right away
+    crate::resolve_promise(promise, Some(0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_pages() {
+        let pages = allocated_pages();
+        assert!(request_pages(2).unwrap() >= pages);
+
+        // Other tests might be requesting pages at the same time
+        assert!(allocated_pages() >= pages + 2);
+
+        // Growing past the limit fails without changing anything
+        let pages = allocated_pages();
+        assert_eq!(request_pages(HEAP_PAGES + 1), None);
+        assert!(allocated_pages() >= pages);
+    }
+
+    #[test]
+    fn test_out_of_memory() {
+        let huge = core::alloc::Layout::from_size_align(HEAP_PAGES * PAGE_SIZE * 2, 8).unwrap();
+
+        assert!(unsafe { try_alloc(huge) }.is_none());
+        assert!(unsafe { try_alloc(layout_of::<u64>()) }.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Out of memory")]
+    fn test_out_of_memory_abort() {
+        let huge = core::alloc::Layout::from_size_align(HEAP_PAGES * PAGE_SIZE * 2, 8).unwrap();
+
+        unsafe { alloc(huge) };
+    }
+
+    #[test]
+    fn test_host_strings() {
+        set_host_string(HostString::UrlParam, "level", Some("forest-2"));
+
+        let mut buf = [0; 16];
+        assert_eq!(read_host_string(HostString::UrlParam, "level", &mut buf), Some("forest-2"));
+        assert_eq!(host_string_len(HostString::UrlParam, "level"), Some(8));
+
+        // Too long to fit, or missing altogether
+        assert_eq!(read_host_string(HostString::UrlParam, "level", &mut buf[..4]), None);
+        assert_eq!(read_host_string(HostString::LocalStorage, "level", &mut buf), None);
+
+        set_host_string(HostString::UrlParam, "level", None);
+        assert_eq!(host_string_len(HostString::UrlParam, "level"), None);
+    }
+
+    #[test]
+    fn test_timers() {
+        #[derive(Default)]
+        struct Respawn {
+            ticks: u32,
+            respawned: bool
+        }
+
+        let mut app = Respawn::default();
+
+        let respawn = set_timeout(1500, |app: &mut Respawn, _| app.respawned = true).unwrap();
+        let tick = set_interval(100, |app: &mut Respawn, _| app.ticks += 1).unwrap();
+
+        assert_eq!(timers(), [
+            Timer { id: respawn, ms: 1500, repeat: false },
+            Timer { id: tick, ms: 100, repeat: true },
+        ]);
+
+        // Timers don't live in the callback table, so its ids can't reach them
+        unregister_callback(tick & ID_INDEX_MASK);
+
+        dispatch_timer(&mut app, tick);
+        dispatch_timer(&mut app, tick);
+        dispatch_timer(&mut app, respawn);
+        assert_eq!((app.ticks, app.respawned), (2, true));
+
+        // Timeouts only ever fire once
+        app.respawned = false;
+        dispatch_timer(&mut app, respawn);
+        assert!(!app.respawned);
+
+        // The id of a timeout that fired stays stale, even once its slot is reused
+        let again = set_timeout(1500, |app: &mut Respawn, _| app.respawned = true).unwrap();
+        assert_ne!(again, respawn);
+
+        clear_timer(respawn);
+        assert!(timers().iter().any(|timer| timer.id == again));
+
+        dispatch_timer(&mut app, again);
+        assert!(app.respawned);
+
+        clear_timer(tick);
+        dispatch_timer(&mut app, tick);
+        assert_eq!(app.ticks, 2);
+        assert!(timers().iter().all(|timer| timer.id != tick));
+    }
+
+    #[test]
+    fn test_app_loop() {
+        #[derive(Default)]
+        struct Clearing {
+            updates: usize,
+            size: (u32, u32)
+        }
+
+        impl AppHandler for Clearing {
+            const TIMESTEP: f32 = 0.125;
+
+            fn update(&mut self, _dt: f32) {
+                self.updates += 1;
+            }
+
+            fn draw(&mut self, _alpha: f32) {
+                unsafe {
+                    glClearColor(0.0, 0.0, 0.0, 1.0);
+                    glClear(GL_COLOR_BUFFER_BIT);
+                }
+            }
+
+            fn resize(&mut self, width: u32, height: u32, _scale: f32) {
+                self.size = (width, height);
+            }
+        }
+
+        let mut app = Clearing::default();
+        let mut clock = FrameClock::new(Clearing::TIMESTEP);
+
+        clear_gl_calls();
+        dispatch_resize(&mut app, 640, 480, 1.0);
+
+        for frame in 0..3 {
+            apply_viewport();
+            clock.run(&mut app, frame as f64 * 187.5);
+        }
+
+        assert_eq!(app.size, (640, 480));
+        assert_eq!(app.updates, 3);
+        assert_eq!(gl_calls(), [
+            "glViewport",
+            "glClearColor", "glClear",
+            "glClearColor", "glClear",
+            "glClearColor", "glClear",
+        ]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Input events forwarded from the JS side
+
+use crate::{AppHandler, ConstVec, StaticCell};
+
+/// A compact set of keys the JS glue forwards to us. Anything else is ignored on the JS side,
+/// so we don't have to pay for a full keyboard table.
+///
+/// NOTE: The discriminants must match the `KEYS` table in `web/glue/runtime.js`
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyCode {
+    Up,
+    Down,
+    Left,
+    Right,
+    W,
+    A,
+    S,
+    D,
+    Space,
+    Escape,
+    Enter,
+}
+
+impl KeyCode {
+    /// Convert a raw key code received from JS. Returns [None] for unknown codes
+    pub fn from_raw(code: u32) -> Option<Self> {
+        Some(match code {
+            0 => Self::Up,
+            1 => Self::Down,
+            2 => Self::Left,
+            3 => Self::Right,
+            4 => Self::W,
+            5 => Self::A,
+            6 => Self::S,
+            7 => Self::D,
+            8 => Self::Space,
+            9 => Self::Escape,
+            10 => Self::Enter,
+            _ => return None
+        })
+    }
+}
+
+/// Mouse buttons, matching `MouseEvent.button` in the browser
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+impl MouseButton {
+    /// Convert a raw button received from JS. Returns [None] for extra buttons
+    pub fn from_raw(button: u32) -> Option<Self> {
+        Some(match button {
+            0 => Self::Left,
+            1 => Self::Middle,
+            2 => Self::Right,
+            _ => return None
+        })
+    }
+}
+
+/// The maximum amount of simultaneously tracked touches. Any extra fingers are ignored
+pub const MAX_TOUCHES: usize = 10;
+
+/// A single finger on the screen
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Touch {
+    /// The browser's touch identifier. It stays the same for as long as the finger is down
+    pub id: u32,
+    pub x: f32,
+    pub y: f32
+}
+
+/// The kind of a touch event
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TouchPhase {
+    Start,
+    Move,
+    End,
+    Cancel
+}
+
+/// A fixed-capacity table of active touches, so that no allocation happens per event
+pub struct Touches {
+    active: ConstVec<Touch, MAX_TOUCHES>
+}
+
+impl Touches {
+    pub const fn new() -> Self {
+        Self {
+            active: ConstVec::new()
+        }
+    }
+
+    /// All the touches that are currently down
+    pub fn as_slice(&self) -> &[Touch] {
+        self.active.as_slice()
+    }
+
+    fn position(&self, id: u32) -> Option<usize> {
+        self.as_slice().iter().position(|touch| touch.id == id)
+    }
+
+    /// Get an active touch by its identifier
+    pub fn get(&self, id: u32) -> Option<&Touch> {
+        self.as_slice().iter().find(|touch| touch.id == id)
+    }
+
+    /// Update the table, returning the event to forward to the app. Events for touches that
+    /// aren't tracked (because the table was full when they started) are dropped.
+    pub fn update(&mut self, phase: TouchPhase, touch: Touch) -> Option<TouchPhase> {
+        let position = self.position(touch.id);
+
+        match (phase, position) {
+            (TouchPhase::Start, None) => {
+                if self.active.is_full() {
+                    return None;
+                }
+
+                self.active.push(touch);
+                Some(TouchPhase::Start)
+            },
+            (TouchPhase::Start | TouchPhase::Move, Some(index)) => {
+                self.active.set(index, touch);
+                Some(TouchPhase::Move)
+            },
+            (TouchPhase::End | TouchPhase::Cancel, Some(index)) => {
+                self.active.swap_pop(index);
+                Some(phase)
+            },
+            (_, None) => None
+        }
+    }
+
+    /// Update the table and forward the event to the app. See [Touches::update]
+    pub fn dispatch<A: AppHandler>(&mut self, app: &mut A, phase: TouchPhase, touch: Touch) {
+        if let Some(phase) = self.update(phase, touch) {
+            forward_touch(app, phase, touch);
+        }
+    }
+}
+
+fn forward_touch<A: AppHandler>(app: &mut A, phase: TouchPhase, touch: Touch) {
+    match phase {
+        TouchPhase::Start => app.touch_start(touch),
+        TouchPhase::Move => app.touch_move(touch),
+        TouchPhase::End => app.touch_end(touch),
+        TouchPhase::Cancel => app.touch_cancel(touch)
+    }
+}
+
+impl Default for Touches {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static TOUCHES: StaticCell<Touches> = StaticCell::new(Touches::new());
+
+/// A copy of all the touches that are currently down
+pub fn touches() -> ConstVec<Touch, MAX_TOUCHES> {
+    let mut touches = ConstVec::new();
+    for &touch in unsafe { TOUCHES.get() }.as_slice() {
+        touches.push(touch);
+    }
+
+    touches
+}
+
+/// Forward a touch event from JS into the global touch table and the app
+pub fn dispatch_touch<A: AppHandler>(app: &mut A, phase: TouchPhase, id: u32, x: f32, y: f32) {
+    let touch = Touch { id, x, y };
+
+    // The table isn't borrowed anymore by the time the app runs, so it can call [touches]
+    if let Some(phase) = unsafe { TOUCHES.get_mut() }.update(phase, touch) {
+        forward_touch(app, phase, touch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: [usize; 4]
+    }
+
+    impl AppHandler for Recorder {
+        fn draw(&mut self, _alpha: f32) {}
+
+        fn touch_start(&mut self, _touch: Touch) {
+            self.events[0] += 1;
+        }
+
+        fn touch_move(&mut self, _touch: Touch) {
+            self.events[1] += 1;
+        }
+
+        fn touch_end(&mut self, _touch: Touch) {
+            self.events[2] += 1;
+        }
+
+        fn touch_cancel(&mut self, _touch: Touch) {
+            self.events[3] += 1;
+        }
+    }
+
+    fn touch(id: u32, x: f32) -> Touch {
+        Touch { id, x, y: 0.0 }
+    }
+
+    #[test]
+    fn test_touches() {
+        let mut touches = Touches::new();
+        let mut app = Recorder::default();
+
+        touches.dispatch(&mut app, TouchPhase::Start, touch(7, 1.0));
+        touches.dispatch(&mut app, TouchPhase::Start, touch(3, 2.0));
+        touches.dispatch(&mut app, TouchPhase::Move, touch(7, 5.0));
+
+        assert_eq!(touches.as_slice().len(), 2);
+        assert_eq!(touches.get(7).unwrap().x, 5.0);
+
+        touches.dispatch(&mut app, TouchPhase::End, touch(7, 5.0));
+        touches.dispatch(&mut app, TouchPhase::Cancel, touch(3, 2.0));
+
+        // Unknown touches are ignored
+        touches.dispatch(&mut app, TouchPhase::Move, touch(3, 2.0));
+
+        assert!(touches.as_slice().is_empty());
+        assert_eq!(app.events, [2, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_touches_capacity() {
+        let mut touches = Touches::new();
+        let mut app = Recorder::default();
+
+        for id in 0..(MAX_TOUCHES as u32 + 2) {
+            touches.dispatch(&mut app, TouchPhase::Start, touch(id, 0.0));
+        }
+
+        assert_eq!(touches.as_slice().len(), MAX_TOUCHES);
+        assert_eq!(app.events[0], MAX_TOUCHES);
+
+        // The overflowing finger was never tracked, so its end is dropped too
+        touches.dispatch(&mut app, TouchPhase::End, touch(MAX_TOUCHES as u32, 0.0));
+        assert_eq!(app.events[2], 0);
+
+        // Ending the last touch in the table doesn't move the others
+        touches.dispatch(&mut app, TouchPhase::End, touch(MAX_TOUCHES as u32 - 1, 0.0));
+        assert_eq!(touches.as_slice().len(), MAX_TOUCHES - 1);
+        assert_eq!(touches.as_slice()[0].id, 0);
+    }
+}
@@ -1,26 +1,59 @@
-use core::ptr::null;
+use core::{alloc::Layout, ptr::null};
 
+use crate::{Callback, Level, StaticCell, alloc, alloc_slice_fill};
+
+#[cfg(not(target_family = "wasm"))]
+use crate::host::*;
+
+#[cfg(target_family = "wasm")]
 unsafe extern "C" {        
-    fn js_request_pages(pages: usize);
+    #[link_name = crate::wasm_name!(js_request_pages)]
+    fn js_request_pages(pages: usize) -> isize;
 
+    #[link_name = crate::wasm_name!(js_allocated_pages)]
     fn js_allocated_pages() -> usize;
 
+    #[link_name = crate::wasm_name!(js_println)]
     fn js_println(start: *const u8, len: usize);
 
+    #[link_name = crate::wasm_name!(js_log)]
+    fn js_log(level: u32, start: *const u8, len: usize);
+
+    #[link_name = crate::wasm_name!(js_alert)]
     fn js_alert(start: *const u8, len: usize);
 
+    #[link_name = crate::wasm_name!(js_panic)]
     fn js_panic(
         err: *const u8, err_len: usize,
         file: *const u8, file_len: usize,
         line: u32
     );
 
+    #[link_name = crate::wasm_name!(js_println_number)]
     fn js_println_number(number: usize);
+
+    #[link_name = crate::wasm_name!(js_read_string)]
+    fn js_read_string(
+        source: u32,
+        key: *const u8, key_len: usize,
+        dst: *mut u8, capacity: usize
+    ) -> isize;
+
+    #[link_name = crate::wasm_name!(js_set_timer)]
+    fn js_set_timer(id: u32, ms: u32, repeat: u32);
+
+    #[link_name = crate::wasm_name!(js_clear_timer)]
+    fn js_clear_timer(id: u32);
+
+    #[cfg(feature = "executor")]
+    #[link_name = crate::wasm_name!(js_sleep)]
+    fn js_sleep(promise: u32, ms: u32);
 }
 
-/// Request an amount of pages to be allocated from JS 
-pub fn request_pages(pages: usize) {
-    unsafe { js_request_pages(pages) }
+/// Request an amount of pages to be allocated from JS. Returns the previous amount of pages, or
+/// [None] if memory can't grow anymore
+pub fn request_pages(pages: usize) -> Option<usize> {
+    usize::try_from(unsafe { js_request_pages(pages) }).ok()
 }
 
 /// Get the current amount of allocated pages from JS
@@ -37,10 +70,17 @@ pub fn println(message: &str) {
     unsafe { println_raw(message.as_ptr(), message.len()) }
 }
 
+/// # Safety
+/// `ptr` must point to `len` bytes of valid UTF-8
 pub unsafe fn println_raw(ptr: *const u8, len: usize) {
     unsafe { js_println(ptr, len);}
 }
 
+/// Print to the console at a specific level. See the `log_*` macros
+pub fn log_str(level: Level, message: &str) {
+    unsafe { js_log(level as u32, message.as_ptr(), message.len()) }
+}
+
 /// Alert!!
 pub fn alert(message: &str) {
     unsafe { js_alert(message.as_ptr(), message.len()) }
@@ -57,4 +97,172 @@ pub fn panic(message: Option<&str>, file: &str, line: u32) {
     };
 
     unsafe { js_panic(err_ptr, err_len, file.as_ptr(), file.len(), line) }
-}
\ No newline at end of file
+}
+
+/// Allocate memory on the behalf of JS, so that it can hand over data of any size (like
+/// the strings returned by `glGetString`). Exported as `__alloc` by [crate::make_app].
+///
+/// The memory is permanent, so JS should only ever do this once per value
+pub fn host_alloc(size: usize, align: usize) -> *mut u8 {
+    match Layout::from_size_align(size, align) {
+        Ok(layout) => unsafe { alloc(layout) },
+        Err(_) => core::ptr::null_mut()
+    }
+}
+
+/// The places JS can read strings from
+///
+/// NOTE: The discriminants must match `js_read_string` in `web/glue/js.js`
+#[repr(u32)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HostString {
+    /// `localStorage.getItem(key)`
+    LocalStorage = 1,
+
+    /// A query parameter of the page's URL
+    UrlParam
+}
+
+/// Get the length in bytes of a host string, or `None` if it doesn't exist
+pub fn host_string_len(source: HostString, key: &str) -> Option<usize> {
+    let len = unsafe {
+        js_read_string(source as u32, key.as_ptr(), key.len(), core::ptr::null_mut(), 0)
+    };
+
+    usize::try_from(len).ok()
+}
+
+/// Read a host string into `buf`.
+///
+/// JS only writes the string if it fits whole, so this returns `None` both when the string
+/// doesn't exist and when it's longer than `buf` (see [host_string_len])
+pub fn read_host_string<'a>(source: HostString, key: &str, buf: &'a mut [u8]) -> Option<&'a str> {
+    let len = unsafe {
+        js_read_string(source as u32, key.as_ptr(), key.len(), buf.as_mut_ptr(), buf.len())
+    };
+
+    let bytes = buf.get(..usize::try_from(len).ok()?)?;
+
+    // JS encodes strings with `TextEncoder`, which only ever produces valid UTF-8
+    Some(unsafe { str::from_utf8_unchecked(bytes) })
+}
+
+/// Read a host string into a permanent allocation of exactly its size
+pub fn read_host_string_static(source: HostString, key: &str) -> Option<&'static str> {
+    let len = host_string_len(source, key)?;
+
+    read_host_string(source, key, alloc_slice_fill(len, 0u8))
+}
+
+/// Identifies a timer. The lower bits hold the index of its slot, and the upper bits a
+/// generation, so that stale ids of timeouts that already fired are ignored
+pub type TimerId = u32;
+
+/// The maximum amount of timers pending at the same time
+pub const MAX_TIMERS: usize = 32;
+
+/// Timer and promise ids hold the index of their slot in the lower bits, and a generation in
+/// the upper ones
+pub(crate) const ID_INDEX_BITS: u32 = 16;
+pub(crate) const ID_INDEX_MASK: u32 = (1 << ID_INDEX_BITS) - 1;
+pub(crate) const ID_GENERATION_MASK: u32 = u32::MAX >> ID_INDEX_BITS;
+
+#[derive(Clone, Copy)]
+enum TimerState {
+    Free,
+    Timeout(Callback),
+    Interval(Callback)
+}
+
+#[derive(Clone, Copy)]
+struct TimerSlot {
+    generation: u32,
+    state: TimerState
+}
+
+/// Timers own their callbacks, rather than sharing the callback table, so that
+/// [crate::unregister_callback] can't break a pending timer
+static TIMER_SLOTS: StaticCell<[TimerSlot; MAX_TIMERS]> = StaticCell::new(
+    [TimerSlot { generation: 0, state: TimerState::Free }; MAX_TIMERS]
+);
+
+/// Get the slot of a pending timer, or [None] if the id is stale
+fn timer_slot(id: TimerId) -> Option<&'static mut TimerSlot> {
+    let slot = unsafe { TIMER_SLOTS.get_mut() }.get_mut((id & ID_INDEX_MASK) as usize)?;
+    let pending = !matches!(slot.state, TimerState::Free);
+
+    (pending && slot.generation == id >> ID_INDEX_BITS).then_some(slot)
+}
+
+/// Free a timer's slot, so that its id goes stale
+fn free_timer(slot: &mut TimerSlot) {
+    slot.state = TimerState::Free;
+    slot.generation = (slot.generation + 1) & ID_GENERATION_MASK;
+}
+
+fn set_timer<A: 'static>(ms: u32, callback: fn(&mut A, TimerId), repeat: bool) -> Option<TimerId> {
+    let (index, slot) = unsafe { TIMER_SLOTS.get_mut() }
+        .iter_mut()
+        .enumerate()
+        .find(|(_, slot)| matches!(slot.state, TimerState::Free))?;
+
+    let callback = Callback::from_fn(callback);
+    slot.state = if repeat { TimerState::Interval(callback) } else { TimerState::Timeout(callback) };
+
+    let id = slot.generation << ID_INDEX_BITS | index as u32;
+    unsafe { js_set_timer(id, ms, repeat as u32) };
+
+    Some(id)
+}
+
+/// Call `callback` once after `ms` milliseconds. Returns [None] if [MAX_TIMERS] timers are
+/// already pending
+pub fn set_timeout<A: 'static>(ms: u32, callback: fn(&mut A, TimerId)) -> Option<TimerId> {
+    set_timer(ms, callback, false)
+}
+
+/// Call `callback` every `ms` milliseconds until the timer is cleared. Returns [None] if
+/// [MAX_TIMERS] timers are already pending
+pub fn set_interval<A: 'static>(ms: u32, callback: fn(&mut A, TimerId)) -> Option<TimerId> {
+    set_timer(ms, callback, true)
+}
+
+/// Stop a timer. Does nothing if it's already stopped, or if it was a timeout that fired
+pub fn clear_timer(id: TimerId) {
+    let Some(slot) = timer_slot(id) else {
+        return;
+    };
+
+    free_timer(slot);
+
+    unsafe { js_clear_timer(id) };
+}
+
+/// Forward a `__timer` call from JS to the timer's callback
+pub fn dispatch_timer<A: 'static>(app: &mut A, id: TimerId) {
+    let Some(slot) = timer_slot(id) else {
+        return;
+    };
+
+    // The callback is copied out first, so that it can set or clear timers itself
+    let callback = match slot.state {
+        TimerState::Interval(callback) => callback,
+        TimerState::Timeout(callback) => {
+            free_timer(slot);
+            callback
+        },
+        TimerState::Free => return
+    };
+
+    callback.dispatch(app, id);
+}
+
+/// Wait for `ms` milliseconds inside of a task. Resolves to [None] right away if too many
+/// promises are pending
+#[cfg(feature = "executor")]
+pub async fn sleep(ms: u32) -> Option<()> {
+    let future = crate::JsFuture::new()?;
+    unsafe { js_sleep(future.id(), ms) };
+
+    future.await.map(|_| ())
+}
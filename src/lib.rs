@@ -4,17 +4,55 @@
 #[cfg(not(target_family = "wasm"))]
 extern crate std;
 
+// `Vec`, `Box` and friends. Renamed, since our own allocator lives in `mod alloc`
+#[cfg(feature = "global-alloc")]
+extern crate alloc as collections;
+
 mod cell;
 pub use cell::*;
 
 mod alloc;
+pub use alloc::*;
+
 mod app;
 
 pub use app::*;
 
+mod names;
+
 mod js;
 pub use js::*;
 
+#[cfg(not(target_family = "wasm"))]
+pub mod host;
+
+mod fmt;
+pub use fmt::*;
+
+mod log;
+pub use log::*;
+
+mod input;
+pub use input::*;
+
+mod gamepad;
+pub use gamepad::*;
+
+mod time;
+pub use time::*;
+
+mod canvas;
+pub use canvas::*;
+
+mod callback;
+pub use callback::*;
+
+#[cfg(feature = "executor")]
+mod executor;
+#[cfg(feature = "executor")]
+pub use executor::*;
+
+// Still a work in progress
 mod game;
 
 // use crate::alloc2::alloc;
@@ -24,7 +62,7 @@ pub use gl::*;
 
 struct App;
 impl AppHandler for App {
-    fn draw(&mut self) {
+    fn draw(&mut self, _alpha: f32) {
         
     }
 }
@@ -0,0 +1,70 @@
+//! Leveled logging into the browser console.
+//!
+//! Arguments are rendered through [crate::Render], so integers, floats and strings can be
+//! interpolated without `core::fmt`:
+//! ```ignore
+//! log_info!("score: ", score, ", speed: ", speed);
+//! ```
+//! Anything above [MAX_LEVEL] is compiled out entirely. The level is lowered through the
+//! `max-level-*` cargo features.
+
+/// Log levels, mapping onto `console.error/warn/info/debug`
+///
+/// NOTE: The discriminants must match `js_log` in `web/glue/js.js`
+#[repr(u32)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug
+}
+
+/// The most verbose level that still gets logged. `0` disables logging altogether
+pub const MAX_LEVEL: u32 = if cfg!(feature = "max-level-off") {
+    0
+} else if cfg!(feature = "max-level-error") {
+    Level::Error as u32
+} else if cfg!(feature = "max-level-warn") {
+    Level::Warn as u32
+} else if cfg!(feature = "max-level-info") {
+    Level::Info as u32
+} else {
+    Level::Debug as u32
+};
+
+/// The size of the stack buffer a single log message is rendered into
+pub const LOG_MESSAGE_SIZE: usize = 256;
+
+/// Log a message at the provided [Level]. Prefer the level-specific macros
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:expr),+ $(,)?) => {
+        if ($level as u32) <= $crate::MAX_LEVEL {
+            let mut message: $crate::StackStr<{ $crate::LOG_MESSAGE_SIZE }> = $crate::StackStr::new();
+            $( $crate::Render::render(&$arg, &mut message); )+
+
+            $crate::log_str($level, message.as_str());
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:expr),+ $(,)?) => { $crate::log!($crate::Level::Debug, $($arg),+) };
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:expr),+ $(,)?) => { $crate::log!($crate::Level::Info, $($arg),+) };
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:expr),+ $(,)?) => { $crate::log!($crate::Level::Warn, $($arg),+) };
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:expr),+ $(,)?) => { $crate::log!($crate::Level::Error, $($arg),+) };
+}
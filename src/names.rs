@@ -0,0 +1,310 @@
+//! Short names for every wasm import and export.
+//!
+//! Names of imports and exports are stored as strings inside the binary, which adds up quickly
+//! with names like `glGetShaderInfoLog`. With the `short-names` feature every one of them is
+//! replaced with its entry from [short_name], and `./glue --short` emits the JS side with the
+//! same table.
+//!
+//! NOTE: Every new import or export needs an entry here, otherwise `short-names` builds fail
+
+/// The name an import or export has in the binary
+#[cfg(feature = "short-names")]
+#[macro_export]
+macro_rules! wasm_name {
+    ($name:ident) => { $crate::short_name!($name) };
+}
+
+/// The name an import or export has in the binary
+#[cfg(not(feature = "short-names"))]
+#[macro_export]
+macro_rules! wasm_name {
+    ($name:ident) => { stringify!($name) };
+}
+
+/// The mapping table itself
+#[macro_export]
+macro_rules! short_name {
+    (__main) => { "a" };
+    (__draw) => { "b" };
+    (__resize) => { "c" };
+    (__suspended) => { "d" };
+    (__resumed) => { "e" };
+    (__before_unload) => { "f" };
+    (__key_down) => { "g" };
+    (__key_up) => { "h" };
+    (__pointer_move) => { "i" };
+    (__pointer_down) => { "j" };
+    (__pointer_up) => { "k" };
+    (__wheel) => { "l" };
+    (__touch_start) => { "m" };
+    (__touch_move) => { "n" };
+    (__touch_end) => { "o" };
+    (__touch_cancel) => { "p" };
+    (__gamepads) => { "q" };
+    (__gamepad_connected) => { "r" };
+    (__gamepad_disconnected) => { "s" };
+    (js_request_pages) => { "t" };
+    (js_allocated_pages) => { "u" };
+    (js_println) => { "v" };
+    (js_log) => { "w" };
+    (js_alert) => { "x" };
+    (js_panic) => { "y" };
+    (js_println_number) => { "z" };
+    (glActiveTexture) => { "A" };
+    (glAttachShader) => { "B" };
+    (glBindAttribLocation) => { "C" };
+    (glBindBuffer) => { "D" };
+    (glBindFramebuffer) => { "E" };
+    (glBindRenderbuffer) => { "F" };
+    (glBindTexture) => { "G" };
+    (glBlendColor) => { "H" };
+    (glBlendEquation) => { "I" };
+    (glBlendEquationSeparate) => { "J" };
+    (glBlendFunc) => { "K" };
+    (glBlendFuncSeparate) => { "L" };
+    (glBufferData) => { "M" };
+    (glBufferSubData) => { "N" };
+    (glCheckFramebufferStatus) => { "O" };
+    (glClear) => { "P" };
+    (glClearColor) => { "Q" };
+    (glClearDepthf) => { "R" };
+    (glClearStencil) => { "S" };
+    (glColorMask) => { "T" };
+    (glCompileShader) => { "U" };
+    (glCompressedTexImage2D) => { "V" };
+    (glCompressedTexSubImage2D) => { "W" };
+    (glCopyTexImage2D) => { "X" };
+    (glCopyTexSubImage2D) => { "Y" };
+    (glCreateProgram) => { "Z" };
+    (glCreateShader) => { "aa" };
+    (glCullFace) => { "ab" };
+    (glDeleteBuffers) => { "ac" };
+    (glDeleteFramebuffers) => { "ad" };
+    (glDeleteProgram) => { "ae" };
+    (glDeleteRenderbuffers) => { "af" };
+    (glDeleteShader) => { "ag" };
+    (glDeleteTextures) => { "ah" };
+    (glDepthFunc) => { "ai" };
+    (glDepthMask) => { "aj" };
+    (glDepthRangef) => { "ak" };
+    (glDetachShader) => { "al" };
+    (glDisable) => { "am" };
+    (glDisableVertexAttribArray) => { "an" };
+    (glDrawArrays) => { "ao" };
+    (glDrawElements) => { "ap" };
+    (glEnable) => { "aq" };
+    (glEnableVertexAttribArray) => { "ar" };
+    (glFinish) => { "as" };
+    (glFlush) => { "at" };
+    (glFramebufferRenderbuffer) => { "au" };
+    (glFramebufferTexture2D) => { "av" };
+    (glFrontFace) => { "aw" };
+    (glGenBuffers) => { "ax" };
+    (glGenerateMipmap) => { "ay" };
+    (glGenFramebuffers) => { "az" };
+    (glGenRenderbuffers) => { "aA" };
+    (glGenTextures) => { "aB" };
+    (glGetActiveAttrib) => { "aC" };
+    (glGetActiveUniform) => { "aD" };
+    (glGetAttachedShaders) => { "aE" };
+    (glGetAttribLocation) => { "aF" };
+    (glGetBooleanv) => { "aG" };
+    (glGetBufferParameteriv) => { "aH" };
+    (glGetError) => { "aI" };
+    (glGetFloatv) => { "aJ" };
+    (glGetFramebufferAttachmentParameteriv) => { "aK" };
+    (glGetIntegerv) => { "aL" };
+    (glGetProgramiv) => { "aM" };
+    (glGetProgramInfoLog) => { "aN" };
+    (glGetRenderbufferParameteriv) => { "aO" };
+    (glGetShaderiv) => { "aP" };
+    (glGetShaderInfoLog) => { "aQ" };
+    (glGetShaderPrecisionFormat) => { "aR" };
+    (glGetShaderSource) => { "aS" };
+    (glGetString) => { "aT" };
+    (glGetTexParameterfv) => { "aU" };
+    (glGetTexParameteriv) => { "aV" };
+    (glGetUniformfv) => { "aW" };
+    (glGetUniformiv) => { "aX" };
+    (glGetUniformLocation) => { "aY" };
+    (glGetVertexAttribfv) => { "aZ" };
+    (glGetVertexAttribiv) => { "ba" };
+    (glGetVertexAttribPointerv) => { "bb" };
+    (glHint) => { "bc" };
+    (glIsBuffer) => { "bd" };
+    (glIsEnabled) => { "be" };
+    (glIsFramebuffer) => { "bf" };
+    (glIsProgram) => { "bg" };
+    (glIsRenderbuffer) => { "bh" };
+    (glIsShader) => { "bi" };
+    (glIsTexture) => { "bj" };
+    (glLineWidth) => { "bk" };
+    (glLinkProgram) => { "bl" };
+    (glPixelStorei) => { "bm" };
+    (glPolygonOffset) => { "bn" };
+    (glReadPixels) => { "bo" };
+    (glReleaseShaderCompiler) => { "bp" };
+    (glRenderbufferStorage) => { "bq" };
+    (glSampleCoverage) => { "br" };
+    (glScissor) => { "bs" };
+    (glShaderBinary) => { "bt" };
+    (glShaderSource) => { "bu" };
+    (glStencilFunc) => { "bv" };
+    (glStencilFuncSeparate) => { "bw" };
+    (glStencilMask) => { "bx" };
+    (glStencilMaskSeparate) => { "by" };
+    (glStencilOp) => { "bz" };
+    (glStencilOpSeparate) => { "bA" };
+    (glTexImage2D) => { "bB" };
+    (glTexParameterf) => { "bC" };
+    (glTexParameterfv) => { "bD" };
+    (glTexParameteri) => { "bE" };
+    (glTexParameteriv) => { "bF" };
+    (glTexSubImage2D) => { "bG" };
+    (glUniform1f) => { "bH" };
+    (glUniform1fv) => { "bI" };
+    (glUniform1i) => { "bJ" };
+    (glUniform1iv) => { "bK" };
+    (glUniform2f) => { "bL" };
+    (glUniform2fv) => { "bM" };
+    (glUniform2i) => { "bN" };
+    (glUniform2iv) => { "bO" };
+    (glUniform3f) => { "bP" };
+    (glUniform3fv) => { "bQ" };
+    (glUniform3i) => { "bR" };
+    (glUniform3iv) => { "bS" };
+    (glUniform4f) => { "bT" };
+    (glUniform4fv) => { "bU" };
+    (glUniform4i) => { "bV" };
+    (glUniform4iv) => { "bW" };
+    (glUniformMatrix2fv) => { "bX" };
+    (glUniformMatrix3fv) => { "bY" };
+    (glUniformMatrix4fv) => { "bZ" };
+    (glUseProgram) => { "ca" };
+    (glValidateProgram) => { "cb" };
+    (glVertexAttrib1f) => { "cc" };
+    (glVertexAttrib1fv) => { "cd" };
+    (glVertexAttrib2f) => { "ce" };
+    (glVertexAttrib2fv) => { "cf" };
+    (glVertexAttrib3f) => { "cg" };
+    (glVertexAttrib3fv) => { "ch" };
+    (glVertexAttrib4f) => { "ci" };
+    (glVertexAttrib4fv) => { "cj" };
+    (glVertexAttribPointer) => { "ck" };
+    (glVertexAttribIPointer) => { "cl" };
+    (glViewport) => { "cm" };
+    (glReadBuffer) => { "cn" };
+    (glDrawRangeElements) => { "co" };
+    (glTexImage3D) => { "cp" };
+    (glTexSubImage3D) => { "cq" };
+    (glCopyTexSubImage3D) => { "cr" };
+    (glCompressedTexImage3D) => { "cs" };
+    (glCompressedTexSubImage3D) => { "ct" };
+    (glGenQueries) => { "cu" };
+    (glDeleteQueries) => { "cv" };
+    (glIsQuery) => { "cw" };
+    (glBeginQuery) => { "cx" };
+    (glEndQuery) => { "cy" };
+    (glGetQueryiv) => { "cz" };
+    (glQueryCounter) => { "cA" };
+    (glGetQueryObjectiv) => { "cB" };
+    (glGetQueryObjectui64v) => { "cC" };
+    (glUnmapBuffer) => { "cD" };
+    (glGetBufferPointerv) => { "cE" };
+    (glDrawBuffers) => { "cF" };
+    (glUniformMatrix2x3fv) => { "cG" };
+    (glUniformMatrix3x2fv) => { "cH" };
+    (glUniformMatrix2x4fv) => { "cI" };
+    (glUniformMatrix4x2fv) => { "cJ" };
+    (glUniformMatrix3x4fv) => { "cK" };
+    (glUniformMatrix4x3fv) => { "cL" };
+    (glBlitFramebuffer) => { "cM" };
+    (glRenderbufferStorageMultisample) => { "cN" };
+    (glFramebufferTextureLayer) => { "cO" };
+    (glMapBufferRange) => { "cP" };
+    (glFlushMappedBufferRange) => { "cQ" };
+    (glBindVertexArray) => { "cR" };
+    (glDeleteVertexArrays) => { "cS" };
+    (glGenVertexArrays) => { "cT" };
+    (glIsVertexArray) => { "cU" };
+    (glGetIntegeri_v) => { "cV" };
+    (glBeginTransformFeedback) => { "cW" };
+    (glEndTransformFeedback) => { "cX" };
+    (glBindBufferRange) => { "cY" };
+    (glBindBufferBase) => { "cZ" };
+    (glTransformFeedbackVaryings) => { "da" };
+    (glGetTransformFeedbackVarying) => { "db" };
+    (glGetVertexAttribIiv) => { "dc" };
+    (glGetVertexAttribIuiv) => { "dd" };
+    (glVertexAttribI4i) => { "de" };
+    (glVertexAttribI4ui) => { "df" };
+    (glVertexAttribI4iv) => { "dg" };
+    (glVertexAttribI4uiv) => { "dh" };
+    (glGetUniformuiv) => { "di" };
+    (glGetFragDataLocation) => { "dj" };
+    (glUniform1ui) => { "dk" };
+    (glUniform2ui) => { "dl" };
+    (glUniform3ui) => { "dm" };
+    (glUniform4ui) => { "dn" };
+    (glUniform1uiv) => { "do" };
+    (glUniform2uiv) => { "dp" };
+    (glUniform3uiv) => { "dq" };
+    (glUniform4uiv) => { "dr" };
+    (glClearBufferiv) => { "ds" };
+    (glClearBufferuiv) => { "dt" };
+    (glClearBufferfv) => { "du" };
+    (glClearBufferfi) => { "dv" };
+    (glGetStringi) => { "dw" };
+    (glCopyBufferSubData) => { "dx" };
+    (glGetUniformIndices) => { "dy" };
+    (glGetActiveUniformsiv) => { "dz" };
+    (glGetUniformBlockIndex) => { "dA" };
+    (glGetActiveUniformBlockiv) => { "dB" };
+    (glGetActiveUniformBlockName) => { "dC" };
+    (glUniformBlockBinding) => { "dD" };
+    (glDrawArraysInstanced) => { "dE" };
+    (glDrawElementsInstanced) => { "dF" };
+    (glFenceSync) => { "dG" };
+    (glIsSync) => { "dH" };
+    (glDeleteSync) => { "dI" };
+    (glClientWaitSync) => { "dJ" };
+    (glWaitSync) => { "dK" };
+    (glGetInteger64v) => { "dL" };
+    (glGetSynciv) => { "dM" };
+    (glGetInteger64i_v) => { "dN" };
+    (glGetBufferParameteri64v) => { "dO" };
+    (glGenSamplers) => { "dP" };
+    (glDeleteSamplers) => { "dQ" };
+    (glIsSampler) => { "dR" };
+    (glBindSampler) => { "dS" };
+    (glSamplerParameteri) => { "dT" };
+    (glSamplerParameteriv) => { "dU" };
+    (glSamplerParameterf) => { "dV" };
+    (glSamplerParameterfv) => { "dW" };
+    (glGetSamplerParameteriv) => { "dX" };
+    (glGetSamplerParameterfv) => { "dY" };
+    (glVertexAttribDivisor) => { "dZ" };
+    (glBindTransformFeedback) => { "ea" };
+    (glDeleteTransformFeedbacks) => { "eb" };
+    (glGenTransformFeedbacks) => { "ec" };
+    (glIsTransformFeedback) => { "ed" };
+    (glPauseTransformFeedback) => { "ee" };
+    (glResumeTransformFeedback) => { "ef" };
+    (glGetProgramBinary) => { "eg" };
+    (glProgramBinary) => { "eh" };
+    (glProgramParameteri) => { "ei" };
+    (glInvalidateFramebuffer) => { "ej" };
+    (glInvalidateSubFramebuffer) => { "ek" };
+    (glTexStorage2D) => { "el" };
+    (glTexStorage3D) => { "em" };
+    (glGetInternalformativ) => { "en" };
+    (__alloc) => { "eo" };
+    (js_read_string) => { "ep" };
+    (__callback) => { "eq" };
+    (__timer) => { "er" };
+    (js_set_timer) => { "es" };
+    (js_clear_timer) => { "et" };
+    (__resolve) => { "eu" };
+    (js_sleep) => { "ev" };
+    (__heap_report) => { "ew" };
+}
@@ -0,0 +1,112 @@
+//! Frame timing. The browser calls us at whatever rate the display runs at, while the game
+//! logic runs at a fixed rate, so an accumulator splits one into the other.
+
+use crate::AppHandler;
+
+/// The largest gap between two frames (in seconds) that we're willing to simulate. Anything
+/// longer (a backgrounded tab, a debugger pause) is clamped to this.
+pub const MAX_FRAME_TIME: f32 = 0.25;
+
+pub struct FrameClock {
+    /// The last timestamp in milliseconds
+    last: Option<f64>,
+    accumulator: f32,
+    timestep: f32
+}
+
+impl FrameClock {
+    /// Create a clock that steps every `timestep` seconds
+    pub const fn new(timestep: f32) -> Self {
+        Self {
+            last: None,
+            accumulator: 0.0,
+            timestep
+        }
+    }
+
+    /// Advance the clock to a new `requestAnimationFrame` timestamp (in milliseconds).
+    ///
+    /// Returns the delta time in seconds, clamped to [MAX_FRAME_TIME]
+    pub fn advance(&mut self, time: f64) -> f32 {
+        let dt = match self.last {
+            Some(last) => ((time - last) / 1000.0) as f32,
+            None => 0.0
+        };
+        let dt = dt.clamp(0.0, MAX_FRAME_TIME);
+
+        self.last = Some(time);
+        self.accumulator += dt;
+
+        dt
+    }
+
+    /// Consume a single fixed step from the accumulator, if there's enough time for one
+    pub fn step(&mut self) -> bool {
+        if self.accumulator >= self.timestep {
+            self.accumulator -= self.timestep;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// How far we are into the next fixed step, in the `0..1` range
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / self.timestep
+    }
+
+    /// Forget the last timestamp, so that the next frame doesn't count the time in between
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.accumulator = 0.0;
+    }
+
+    /// Run a single frame of the app: all the pending fixed updates, and then a draw
+    pub fn run<A: AppHandler>(&mut self, app: &mut A, time: f64) {
+        self.advance(time);
+
+        while self.step() {
+            app.update(self.timestep);
+        }
+
+        app.draw(self.alpha());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_clock() {
+        let mut clock = FrameClock::new(0.01);
+
+        // The first frame has nothing to compare against
+        assert_eq!(clock.advance(1000.0), 0.0);
+        assert!(!clock.step());
+
+        clock.advance(1025.0);
+        assert!(clock.step());
+        assert!(clock.step());
+        assert!(!clock.step());
+        assert!((clock.alpha() - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_frame_clock_clamp() {
+        let mut clock = FrameClock::new(0.1);
+
+        clock.advance(0.0);
+        assert_eq!(clock.advance(60_000.0), MAX_FRAME_TIME);
+
+        let mut steps = 0;
+        while clock.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 2);
+
+        // After a reset the gap is ignored entirely
+        clock.reset();
+        assert_eq!(clock.advance(120_000.0), 0.0);
+    }
+}
@@ -0,0 +1,1480 @@
+/**
+ * A C-style GLES 3.0 API (as declared in `src/gl/webgl.rs`) on top of `WebGL2RenderingContext`.
+ *
+ * GL objects are referred to by integer handles on the Rust side, so every object kind gets its
+ * own handle table. Out-parameters are written straight into linear memory.
+ *
+ * Expects `instance`, `memory` and `ctx` to be defined by the runtime by the time any of these are
+ * called.
+ */
+
+// @helper constants
+const GL_INFO_LOG_LENGTH = 0x8B84;
+const GL_SHADER_SOURCE_LENGTH = 0x8B88;
+const GL_ACTIVE_UNIFORM_MAX_LENGTH = 0x8B87;
+const GL_ACTIVE_ATTRIBUTE_MAX_LENGTH = 0x8B8A;
+const GL_TRANSFORM_FEEDBACK_VARYING_MAX_LENGTH = 0x8C76;
+const GL_ACTIVE_UNIFORM_BLOCK_MAX_NAME_LENGTH = 0x8A35;
+const GL_EXTENSIONS = 0x1F03;
+const GL_NUM_EXTENSIONS = 0x821D;
+const GL_MAJOR_VERSION = 0x821B;
+const GL_MINOR_VERSION = 0x821C;
+const GL_PIXEL_PACK_BUFFER = 0x88EB;
+const GL_PIXEL_UNPACK_BUFFER = 0x88EC;
+
+// @helper utf8
+const utf8Encoder = new TextEncoder();
+const utf8Decoder = new TextDecoder("utf-8");
+
+// @helper objectHandles
+// Maps GL objects back onto their handles, for queries that return objects
+const objectHandles = new WeakMap();
+
+// @helper Handles: objectHandles
+/**
+ * A table of GL objects. Handle `0` is always `null`, just like in GL
+ */
+class Handles {
+    constructor() {
+        this.objects = [null];
+        this.free = [];
+    }
+
+    add(object) {
+        if (object === null) {
+            return 0;
+        }
+
+        const handle = this.free.length > 0 ? this.free.pop() : this.objects.length;
+        this.objects[handle] = object;
+        objectHandles.set(object, handle);
+
+        return handle;
+    }
+
+    get(handle) {
+        return this.objects[handle] ?? null;
+    }
+
+    remove(handle) {
+        const object = this.get(handle);
+
+        if (object !== null) {
+            this.objects[handle] = null;
+            this.free.push(handle);
+        }
+
+        return object;
+    }
+}
+
+// @helper tables: Handles
+const buffers = new Handles();
+const shaders = new Handles();
+const programs = new Handles();
+const textures = new Handles();
+const framebuffers = new Handles();
+const renderbuffers = new Handles();
+const vertexArrays = new Handles();
+const queries = new Handles();
+const samplers = new Handles();
+const transformFeedbacks = new Handles();
+const syncs = new Handles();
+const uniforms = new Handles();
+
+// @helper forgetUniforms: tables
+// Uniform location handles per program, so that repeated lookups don't grow the table
+const programUniforms = new Map();
+
+// Linking (or deleting) a program invalidates all of its uniform locations
+function forgetUniforms(program) {
+    for (const location of programUniforms.get(program)?.values() ?? []) {
+        uniforms.remove(location);
+    }
+    programUniforms.delete(program);
+}
+
+// @helper pixelBuffers
+// Pixel transfers read offsets instead of pointers while these are bound
+let packBuffer = 0;
+let unpackBuffer = 0;
+
+// @helper heap
+function heapU8() {
+    return new Uint8Array(memory.buffer);
+}
+
+function heapI32() {
+    return new Int32Array(memory.buffer);
+}
+
+function heapU32() {
+    return new Uint32Array(memory.buffer);
+}
+
+function heapF32() {
+    return new Float32Array(memory.buffer);
+}
+
+// @helper heapForType
+/**
+ * The heap view matching a GL data type, for pixel transfers
+ */
+function heapForType(type) {
+    switch (type) {
+        case 0x1400: return Int8Array;
+        case 0x1401: return Uint8Array;
+        case 0x1402: return Int16Array;
+        case 0x1403:
+        case 0x8363:
+        case 0x8033:
+        case 0x8034:
+        case 0x140B:
+        case 0x8D61: return Uint16Array;
+        case 0x1404: return Int32Array;
+        case 0x1406: return Float32Array;
+        default: return Uint32Array;
+    }
+}
+
+// @helper allocCString: utf8, heap
+// Strings handed to wasm through `__alloc` can't be freed, so each one is only allocated once
+const glStrings = new Map();
+
+/**
+ * Get a pointer to a NUL-terminated copy of `string` in wasm memory
+ */
+function allocCString(key, string) {
+    let ptr = glStrings.get(key);
+
+    if (ptr === undefined) {
+        const bytes = utf8Encoder.encode(string);
+        ptr = instance.exports.__alloc(bytes.length + 1, 1);
+
+        // Allocating might have grown the memory, so only get the view afterwards
+        const heap = heapU8();
+        heap.set(bytes, ptr);
+        heap[ptr + bytes.length] = 0;
+
+        glStrings.set(key, ptr);
+    }
+
+    return ptr;
+}
+
+// @helper readCString: utf8, heap
+function readCString(ptr) {
+    const bytes = heapU8();
+    let end = ptr;
+    while (bytes[end] !== 0) {
+        end++;
+    }
+
+    return utf8Decoder.decode(bytes.subarray(ptr, end));
+}
+
+// @helper readCStrings: utf8, heap, readCString
+/**
+ * Read an array of `count` C-strings. `lengths` optionally points to their lengths, where a
+ * negative length means the string is NUL-terminated
+ */
+function readCStrings(count, strings, lengths) {
+    const pointers = heapU32();
+    const sizes = heapI32();
+    const result = [];
+
+    for (let i = 0; i < count; i++) {
+        const ptr = pointers[(strings >> 2) + i];
+        const length = lengths === 0 ? -1 : sizes[(lengths >> 2) + i];
+
+        if (length < 0) {
+            result.push(readCString(ptr));
+        } else {
+            result.push(utf8Decoder.decode(heapU8().subarray(ptr, ptr + length)));
+        }
+    }
+
+    return result;
+}
+
+// @helper writeCString: utf8, heap
+/**
+ * Write a string into a buffer of `size` bytes (including the NUL terminator), and its length
+ * (excluding the terminator) into `lengthPtr` if it isn't null
+ */
+function writeCString(string, size, lengthPtr, dst) {
+    let written = 0;
+
+    if (size > 0 && dst !== 0) {
+        const bytes = utf8Encoder.encode(string ?? "");
+        written = Math.min(bytes.length, size - 1);
+
+        const heap = heapU8();
+        heap.set(bytes.subarray(0, written), dst);
+        heap[dst + written] = 0;
+    }
+
+    if (lengthPtr !== 0) {
+        heapI32()[lengthPtr >> 2] = written;
+    }
+}
+
+// @helper writeValues: objectHandles
+/**
+ * Write the result of a GL query into memory. Handles booleans, numbers, arrays and GL objects
+ * (which are written as their handles)
+ */
+function writeValues(ptr, value, kind = "i32") {
+    if (ptr === 0) {
+        return;
+    }
+
+    const view = new DataView(memory.buffer);
+    const values = (value !== null && typeof value === "object" && "length" in value)
+        ? Array.from(value)
+        : [value];
+
+    values.forEach((value, index) => {
+        if (value === null || value === undefined) {
+            value = 0;
+        } else if (typeof value === "boolean") {
+            value = value ? 1 : 0;
+        } else if (typeof value === "object") {
+            value = objectHandles.get(value) ?? 0;
+        }
+
+        switch (kind) {
+            case "u8": view.setUint8(ptr + index, value); break;
+            case "f32": view.setFloat32(ptr + index * 4, value, true); break;
+            case "u32": view.setUint32(ptr + index * 4, value, true); break;
+            case "i64": view.setBigInt64(ptr + index * 8, BigInt(value), true); break;
+            default: view.setInt32(ptr + index * 4, value, true); break;
+        }
+    });
+}
+
+// @helper genObjects: heap
+function genObjects(table, create, n, ptr) {
+    const heap = heapU32();
+    for (let i = 0; i < n; i++) {
+        heap[(ptr >> 2) + i] = table.add(create());
+    }
+}
+
+// @helper deleteObjects: heap
+function deleteObjects(table, destroy, n, ptr) {
+    const heap = heapU32();
+    for (let i = 0; i < n; i++) {
+        const object = table.remove(heap[(ptr >> 2) + i]);
+        if (object !== null) {
+            destroy(object);
+        }
+    }
+}
+
+// @helper writeActiveInfo: writeCString, writeValues
+function writeActiveInfo(info, bufSize, length, size, type, name) {
+    if (info === null) {
+        writeCString("", bufSize, length, name);
+        return;
+    }
+
+    writeCString(info.name, bufSize, length, name);
+    writeValues(size, info.size);
+    writeValues(type, info.type, "u32");
+}
+
+// @helper maxNameLength: utf8
+function maxNameLength(program, count, getInfo) {
+    let max = 0;
+    for (let i = 0; i < count; i++) {
+        const info = getInfo(program, i);
+        if (info !== null) {
+            max = Math.max(max, utf8Encoder.encode(info.name).length + 1);
+        }
+    }
+
+    return max;
+}
+
+// @helper unsupported
+function unsupported(name) {
+    console.warn(`${name} isn't supported by WebGL`);
+}
+
+const gl = {
+    glActiveTexture(texture) {
+        ctx.activeTexture(texture);
+    },
+
+    glAttachShader(program, shader) {
+        ctx.attachShader(programs.get(program), shaders.get(shader));
+    },
+
+    glBindAttribLocation(program, index, name) {
+        ctx.bindAttribLocation(programs.get(program), index, readCString(name));
+    },
+
+    glBindBuffer(target, buffer) {
+        if (target === GL_PIXEL_PACK_BUFFER) {
+            packBuffer = buffer;
+        } else if (target === GL_PIXEL_UNPACK_BUFFER) {
+            unpackBuffer = buffer;
+        }
+
+        ctx.bindBuffer(target, buffers.get(buffer));
+    },
+
+    glBindFramebuffer(target, framebuffer) {
+        ctx.bindFramebuffer(target, framebuffers.get(framebuffer));
+    },
+
+    glBindRenderbuffer(target, renderbuffer) {
+        ctx.bindRenderbuffer(target, renderbuffers.get(renderbuffer));
+    },
+
+    glBindTexture(target, texture) {
+        ctx.bindTexture(target, textures.get(texture));
+    },
+
+    glBlendColor(red, green, blue, alpha) {
+        ctx.blendColor(red, green, blue, alpha);
+    },
+
+    glBlendEquation(mode) {
+        ctx.blendEquation(mode);
+    },
+
+    glBlendEquationSeparate(modeRGB, modeAlpha) {
+        ctx.blendEquationSeparate(modeRGB, modeAlpha);
+    },
+
+    glBlendFunc(sfactor, dfactor) {
+        ctx.blendFunc(sfactor, dfactor);
+    },
+
+    glBlendFuncSeparate(sfactorRGB, dfactorRGB, sfactorAlpha, dfactorAlpha) {
+        ctx.blendFuncSeparate(sfactorRGB, dfactorRGB, sfactorAlpha, dfactorAlpha);
+    },
+
+    glBufferData(target, size, data, usage) {
+        if (data === 0) {
+            ctx.bufferData(target, size, usage);
+        } else {
+            ctx.bufferData(target, heapU8(), usage, data, size);
+        }
+    },
+
+    glBufferSubData(target, offset, size, data) {
+        ctx.bufferSubData(target, offset, heapU8(), data, size);
+    },
+
+    glCheckFramebufferStatus(target) {
+        return ctx.checkFramebufferStatus(target);
+    },
+
+    glClear(mask) {
+        ctx.clear(mask);
+    },
+
+    glClearColor(red, green, blue, alpha) {
+        ctx.clearColor(red, green, blue, alpha);
+    },
+
+    glClearDepthf(d) {
+        ctx.clearDepth(d);
+    },
+
+    glClearStencil(s) {
+        ctx.clearStencil(s);
+    },
+
+    glColorMask(red, green, blue, alpha) {
+        ctx.colorMask(!!red, !!green, !!blue, !!alpha);
+    },
+
+    glCompileShader(shader) {
+        ctx.compileShader(shaders.get(shader));
+    },
+
+    glCompressedTexImage2D(target, level, internalformat, width, height, border, imageSize, data) {
+        if (unpackBuffer !== 0) {
+            ctx.compressedTexImage2D(target, level, internalformat, width, height, border, imageSize, data);
+        } else {
+            ctx.compressedTexImage2D(target, level, internalformat, width, height, border, heapU8(), data, imageSize);
+        }
+    },
+
+    glCompressedTexSubImage2D(target, level, xoffset, yoffset, width, height, format, imageSize, data) {
+        if (unpackBuffer !== 0) {
+            ctx.compressedTexSubImage2D(target, level, xoffset, yoffset, width, height, format, imageSize, data);
+        } else {
+            ctx.compressedTexSubImage2D(target, level, xoffset, yoffset, width, height, format, heapU8(), data, imageSize);
+        }
+    },
+
+    glCopyTexImage2D(target, level, internalformat, x, y, width, height, border) {
+        ctx.copyTexImage2D(target, level, internalformat, x, y, width, height, border);
+    },
+
+    glCopyTexSubImage2D(target, level, xoffset, yoffset, x, y, width, height) {
+        ctx.copyTexSubImage2D(target, level, xoffset, yoffset, x, y, width, height);
+    },
+
+    glCreateProgram() {
+        return programs.add(ctx.createProgram());
+    },
+
+    glCreateShader(type) {
+        return shaders.add(ctx.createShader(type));
+    },
+
+    glCullFace(mode) {
+        ctx.cullFace(mode);
+    },
+
+    glDeleteBuffers(n, ptr) {
+        deleteObjects(buffers, (buffer) => ctx.deleteBuffer(buffer), n, ptr);
+    },
+
+    glDeleteFramebuffers(n, ptr) {
+        deleteObjects(framebuffers, (framebuffer) => ctx.deleteFramebuffer(framebuffer), n, ptr);
+    },
+
+    glDeleteProgram(program) {
+        const object = programs.remove(program);
+        if (object === null) {
+            return;
+        }
+
+        forgetUniforms(program);
+        ctx.deleteProgram(object);
+    },
+
+    glDeleteRenderbuffers(n, ptr) {
+        deleteObjects(renderbuffers, (renderbuffer) => ctx.deleteRenderbuffer(renderbuffer), n, ptr);
+    },
+
+    glDeleteShader(shader) {
+        const object = shaders.remove(shader);
+        if (object !== null) {
+            ctx.deleteShader(object);
+        }
+    },
+
+    glDeleteTextures(n, ptr) {
+        deleteObjects(textures, (texture) => ctx.deleteTexture(texture), n, ptr);
+    },
+
+    glDepthFunc(func) {
+        ctx.depthFunc(func);
+    },
+
+    glDepthMask(flag) {
+        ctx.depthMask(!!flag);
+    },
+
+    glDepthRangef(n, f) {
+        ctx.depthRange(n, f);
+    },
+
+    glDetachShader(program, shader) {
+        ctx.detachShader(programs.get(program), shaders.get(shader));
+    },
+
+    glDisable(cap) {
+        ctx.disable(cap);
+    },
+
+    glDisableVertexAttribArray(index) {
+        ctx.disableVertexAttribArray(index);
+    },
+
+    glDrawArrays(mode, first, count) {
+        ctx.drawArrays(mode, first, count);
+    },
+
+    glDrawElements(mode, count, type, indices) {
+        ctx.drawElements(mode, count, type, indices);
+    },
+
+    glEnable(cap) {
+        ctx.enable(cap);
+    },
+
+    glEnableVertexAttribArray(index) {
+        ctx.enableVertexAttribArray(index);
+    },
+
+    glFinish() {
+        ctx.finish();
+    },
+
+    glFlush() {
+        ctx.flush();
+    },
+
+    glFramebufferRenderbuffer(target, attachment, renderbuffertarget, renderbuffer) {
+        ctx.framebufferRenderbuffer(target, attachment, renderbuffertarget, renderbuffers.get(renderbuffer));
+    },
+
+    glFramebufferTexture2D(target, attachment, textarget, texture, level) {
+        ctx.framebufferTexture2D(target, attachment, textarget, textures.get(texture), level);
+    },
+
+    glFrontFace(mode) {
+        ctx.frontFace(mode);
+    },
+
+    glGenBuffers(n, ptr) {
+        genObjects(buffers, () => ctx.createBuffer(), n, ptr);
+    },
+
+    glGenerateMipmap(target) {
+        ctx.generateMipmap(target);
+    },
+
+    glGenFramebuffers(n, ptr) {
+        genObjects(framebuffers, () => ctx.createFramebuffer(), n, ptr);
+    },
+
+    glGenRenderbuffers(n, ptr) {
+        genObjects(renderbuffers, () => ctx.createRenderbuffer(), n, ptr);
+    },
+
+    glGenTextures(n, ptr) {
+        genObjects(textures, () => ctx.createTexture(), n, ptr);
+    },
+
+    glGetActiveAttrib(program, index, bufSize, length, size, type, name) {
+        const info = ctx.getActiveAttrib(programs.get(program), index);
+        writeActiveInfo(info, bufSize, length, size, type, name);
+    },
+
+    glGetActiveUniform(program, index, bufSize, length, size, type, name) {
+        const info = ctx.getActiveUniform(programs.get(program), index);
+        writeActiveInfo(info, bufSize, length, size, type, name);
+    },
+
+    glGetAttachedShaders(program, maxCount, count, ptr) {
+        const attached = (ctx.getAttachedShaders(programs.get(program)) ?? []).slice(0, maxCount);
+
+        writeValues(ptr, attached, "u32");
+        writeValues(count, attached.length);
+    },
+
+    glGetAttribLocation(program, name) {
+        return ctx.getAttribLocation(programs.get(program), readCString(name));
+    },
+
+    glGetBooleanv(pname, data) {
+        writeValues(data, ctx.getParameter(pname), "u8");
+    },
+
+    glGetBufferParameteriv(target, pname, params) {
+        writeValues(params, ctx.getBufferParameter(target, pname));
+    },
+
+    glGetError() {
+        return ctx.getError();
+    },
+
+    glGetFloatv(pname, data) {
+        writeValues(data, ctx.getParameter(pname), "f32");
+    },
+
+    glGetFramebufferAttachmentParameteriv(target, attachment, pname, params) {
+        writeValues(params, ctx.getFramebufferAttachmentParameter(target, attachment, pname));
+    },
+
+    glGetIntegerv(pname, data) {
+        switch (pname) {
+            case GL_NUM_EXTENSIONS:
+                writeValues(data, ctx.getSupportedExtensions().length);
+                break;
+            case GL_MAJOR_VERSION:
+                writeValues(data, 3);
+                break;
+            case GL_MINOR_VERSION:
+                writeValues(data, 0);
+                break;
+            default:
+                writeValues(data, ctx.getParameter(pname));
+        }
+    },
+
+    glGetProgramiv(program, pname, params) {
+        const object = programs.get(program);
+
+        switch (pname) {
+            case GL_INFO_LOG_LENGTH: {
+                const log = ctx.getProgramInfoLog(object) ?? "";
+                writeValues(params, log.length > 0 ? utf8Encoder.encode(log).length + 1 : 0);
+                break;
+            }
+            case GL_ACTIVE_UNIFORM_MAX_LENGTH: {
+                const count = ctx.getProgramParameter(object, ctx.ACTIVE_UNIFORMS);
+                writeValues(params, maxNameLength(object, count, (p, i) => ctx.getActiveUniform(p, i)));
+                break;
+            }
+            case GL_ACTIVE_ATTRIBUTE_MAX_LENGTH: {
+                const count = ctx.getProgramParameter(object, ctx.ACTIVE_ATTRIBUTES);
+                writeValues(params, maxNameLength(object, count, (p, i) => ctx.getActiveAttrib(p, i)));
+                break;
+            }
+            case GL_TRANSFORM_FEEDBACK_VARYING_MAX_LENGTH: {
+                const count = ctx.getProgramParameter(object, ctx.TRANSFORM_FEEDBACK_VARYINGS);
+                writeValues(params, maxNameLength(object, count, (p, i) => ctx.getTransformFeedbackVarying(p, i)));
+                break;
+            }
+            case GL_ACTIVE_UNIFORM_BLOCK_MAX_NAME_LENGTH: {
+                const count = ctx.getProgramParameter(object, ctx.ACTIVE_UNIFORM_BLOCKS);
+                let max = 0;
+                for (let i = 0; i < count; i++) {
+                    const name = ctx.getActiveUniformBlockName(object, i) ?? "";
+                    max = Math.max(max, utf8Encoder.encode(name).length + 1);
+                }
+                writeValues(params, max);
+                break;
+            }
+            default:
+                writeValues(params, ctx.getProgramParameter(object, pname));
+        }
+    },
+
+    glGetProgramInfoLog(program, bufSize, length, infoLog) {
+        writeCString(ctx.getProgramInfoLog(programs.get(program)), bufSize, length, infoLog);
+    },
+
+    glGetRenderbufferParameteriv(target, pname, params) {
+        writeValues(params, ctx.getRenderbufferParameter(target, pname));
+    },
+
+    glGetShaderiv(shader, pname, params) {
+        const object = shaders.get(shader);
+
+        switch (pname) {
+            case GL_INFO_LOG_LENGTH: {
+                const log = ctx.getShaderInfoLog(object) ?? "";
+                writeValues(params, log.length > 0 ? utf8Encoder.encode(log).length + 1 : 0);
+                break;
+            }
+            case GL_SHADER_SOURCE_LENGTH: {
+                const source = ctx.getShaderSource(object) ?? "";
+                writeValues(params, source.length > 0 ? utf8Encoder.encode(source).length + 1 : 0);
+                break;
+            }
+            default:
+                writeValues(params, ctx.getShaderParameter(object, pname));
+        }
+    },
+
+    glGetShaderInfoLog(shader, bufSize, length, infoLog) {
+        writeCString(ctx.getShaderInfoLog(shaders.get(shader)), bufSize, length, infoLog);
+    },
+
+    glGetShaderPrecisionFormat(shadertype, precisiontype, range, precision) {
+        const format = ctx.getShaderPrecisionFormat(shadertype, precisiontype);
+
+        writeValues(range, [format.rangeMin, format.rangeMax]);
+        writeValues(precision, format.precision);
+    },
+
+    glGetShaderSource(shader, bufSize, length, source) {
+        writeCString(ctx.getShaderSource(shaders.get(shader)), bufSize, length, source);
+    },
+
+    glGetString(name) {
+        if (name === GL_EXTENSIONS) {
+            return allocCString(name, (ctx.getSupportedExtensions() ?? []).join(" "));
+        }
+
+        const string = ctx.getParameter(name);
+        return typeof string === "string" ? allocCString(name, string) : 0;
+    },
+
+    glGetTexParameterfv(target, pname, params) {
+        writeValues(params, ctx.getTexParameter(target, pname), "f32");
+    },
+
+    glGetTexParameteriv(target, pname, params) {
+        writeValues(params, ctx.getTexParameter(target, pname));
+    },
+
+    glGetUniformfv(program, location, params) {
+        writeValues(params, ctx.getUniform(programs.get(program), uniforms.get(location)), "f32");
+    },
+
+    glGetUniformiv(program, location, params) {
+        writeValues(params, ctx.getUniform(programs.get(program), uniforms.get(location)));
+    },
+
+    glGetUniformLocation(program, name) {
+        const key = readCString(name);
+
+        let locations = programUniforms.get(program);
+        if (locations === undefined) {
+            locations = new Map();
+            programUniforms.set(program, locations);
+        }
+
+        if (!locations.has(key)) {
+            const location = ctx.getUniformLocation(programs.get(program), key);
+            if (location === null) {
+                return -1;
+            }
+
+            locations.set(key, uniforms.add(location));
+        }
+
+        return locations.get(key);
+    },
+
+    glGetVertexAttribfv(index, pname, params) {
+        writeValues(params, ctx.getVertexAttrib(index, pname), "f32");
+    },
+
+    glGetVertexAttribiv(index, pname, params) {
+        writeValues(params, ctx.getVertexAttrib(index, pname));
+    },
+
+    glGetVertexAttribPointerv(index, pname, pointer) {
+        writeValues(pointer, ctx.getVertexAttribOffset(index, pname), "u32");
+    },
+
+    glHint(target, mode) {
+        ctx.hint(target, mode);
+    },
+
+    glIsBuffer(buffer) {
+        return ctx.isBuffer(buffers.get(buffer)) ? 1 : 0;
+    },
+
+    glIsEnabled(cap) {
+        return ctx.isEnabled(cap) ? 1 : 0;
+    },
+
+    glIsFramebuffer(framebuffer) {
+        return ctx.isFramebuffer(framebuffers.get(framebuffer)) ? 1 : 0;
+    },
+
+    glIsProgram(program) {
+        return ctx.isProgram(programs.get(program)) ? 1 : 0;
+    },
+
+    glIsRenderbuffer(renderbuffer) {
+        return ctx.isRenderbuffer(renderbuffers.get(renderbuffer)) ? 1 : 0;
+    },
+
+    glIsShader(shader) {
+        return ctx.isShader(shaders.get(shader)) ? 1 : 0;
+    },
+
+    glIsTexture(texture) {
+        return ctx.isTexture(textures.get(texture)) ? 1 : 0;
+    },
+
+    glLineWidth(width) {
+        ctx.lineWidth(width);
+    },
+
+    glLinkProgram(program) {
+        forgetUniforms(program);
+        ctx.linkProgram(programs.get(program));
+    },
+
+    glPixelStorei(pname, param) {
+        ctx.pixelStorei(pname, param);
+    },
+
+    glPolygonOffset(factor, units) {
+        ctx.polygonOffset(factor, units);
+    },
+
+    glReadPixels(x, y, width, height, format, type, pixels) {
+        if (packBuffer !== 0) {
+            ctx.readPixels(x, y, width, height, format, type, pixels);
+        } else {
+            const Heap = heapForType(type);
+            ctx.readPixels(x, y, width, height, format, type, new Heap(memory.buffer), pixels / Heap.BYTES_PER_ELEMENT);
+        }
+    },
+
+    glReleaseShaderCompiler() {
+        // Nothing to release in WebGL
+    },
+
+    glRenderbufferStorage(target, internalformat, width, height) {
+        ctx.renderbufferStorage(target, internalformat, width, height);
+    },
+
+    glSampleCoverage(value, invert) {
+        ctx.sampleCoverage(value, !!invert);
+    },
+
+    glScissor(x, y, width, height) {
+        ctx.scissor(x, y, width, height);
+    },
+
+    glShaderBinary(count, handles, binaryformat, binary, length) {
+        unsupported("glShaderBinary");
+    },
+
+    glShaderSource(shader, count, string, length) {
+        const source = readCStrings(count, string, length).join("");
+        ctx.shaderSource(shaders.get(shader), source);
+    },
+
+    glStencilFunc(func, ref, mask) {
+        ctx.stencilFunc(func, ref, mask);
+    },
+
+    glStencilFuncSeparate(face, func, ref, mask) {
+        ctx.stencilFuncSeparate(face, func, ref, mask);
+    },
+
+    glStencilMask(mask) {
+        ctx.stencilMask(mask);
+    },
+
+    glStencilMaskSeparate(face, mask) {
+        ctx.stencilMaskSeparate(face, mask);
+    },
+
+    glStencilOp(fail, zfail, zpass) {
+        ctx.stencilOp(fail, zfail, zpass);
+    },
+
+    glStencilOpSeparate(face, sfail, dpfail, dppass) {
+        ctx.stencilOpSeparate(face, sfail, dpfail, dppass);
+    },
+
+    glTexImage2D(target, level, internalformat, width, height, border, format, type, pixels) {
+        if (unpackBuffer !== 0) {
+            ctx.texImage2D(target, level, internalformat, width, height, border, format, type, pixels);
+        } else if (pixels === 0) {
+            ctx.texImage2D(target, level, internalformat, width, height, border, format, type, null);
+        } else {
+            const Heap = heapForType(type);
+            ctx.texImage2D(target, level, internalformat, width, height, border, format, type, new Heap(memory.buffer), pixels / Heap.BYTES_PER_ELEMENT);
+        }
+    },
+
+    glTexParameterf(target, pname, param) {
+        ctx.texParameterf(target, pname, param);
+    },
+
+    glTexParameterfv(target, pname, params) {
+        ctx.texParameterf(target, pname, heapF32()[params >> 2]);
+    },
+
+    glTexParameteri(target, pname, param) {
+        ctx.texParameteri(target, pname, param);
+    },
+
+    glTexParameteriv(target, pname, params) {
+        ctx.texParameteri(target, pname, heapI32()[params >> 2]);
+    },
+
+    glTexSubImage2D(target, level, xoffset, yoffset, width, height, format, type, pixels) {
+        if (unpackBuffer !== 0) {
+            ctx.texSubImage2D(target, level, xoffset, yoffset, width, height, format, type, pixels);
+        } else {
+            const Heap = heapForType(type);
+            ctx.texSubImage2D(target, level, xoffset, yoffset, width, height, format, type, new Heap(memory.buffer), pixels / Heap.BYTES_PER_ELEMENT);
+        }
+    },
+
+    glUniform1f(location, v0) {
+        ctx.uniform1f(uniforms.get(location), v0);
+    },
+
+    glUniform1fv(location, count, value) {
+        ctx.uniform1fv(uniforms.get(location), heapF32(), value >> 2, count);
+    },
+
+    glUniform1i(location, v0) {
+        ctx.uniform1i(uniforms.get(location), v0);
+    },
+
+    glUniform1iv(location, count, value) {
+        ctx.uniform1iv(uniforms.get(location), heapI32(), value >> 2, count);
+    },
+
+    glUniform2f(location, v0, v1) {
+        ctx.uniform2f(uniforms.get(location), v0, v1);
+    },
+
+    glUniform2fv(location, count, value) {
+        ctx.uniform2fv(uniforms.get(location), heapF32(), value >> 2, count * 2);
+    },
+
+    glUniform2i(location, v0, v1) {
+        ctx.uniform2i(uniforms.get(location), v0, v1);
+    },
+
+    glUniform2iv(location, count, value) {
+        ctx.uniform2iv(uniforms.get(location), heapI32(), value >> 2, count * 2);
+    },
+
+    glUniform3f(location, v0, v1, v2) {
+        ctx.uniform3f(uniforms.get(location), v0, v1, v2);
+    },
+
+    glUniform3fv(location, count, value) {
+        ctx.uniform3fv(uniforms.get(location), heapF32(), value >> 2, count * 3);
+    },
+
+    glUniform3i(location, v0, v1, v2) {
+        ctx.uniform3i(uniforms.get(location), v0, v1, v2);
+    },
+
+    glUniform3iv(location, count, value) {
+        ctx.uniform3iv(uniforms.get(location), heapI32(), value >> 2, count * 3);
+    },
+
+    glUniform4f(location, v0, v1, v2, v3) {
+        ctx.uniform4f(uniforms.get(location), v0, v1, v2, v3);
+    },
+
+    glUniform4fv(location, count, value) {
+        ctx.uniform4fv(uniforms.get(location), heapF32(), value >> 2, count * 4);
+    },
+
+    glUniform4i(location, v0, v1, v2, v3) {
+        ctx.uniform4i(uniforms.get(location), v0, v1, v2, v3);
+    },
+
+    glUniform4iv(location, count, value) {
+        ctx.uniform4iv(uniforms.get(location), heapI32(), value >> 2, count * 4);
+    },
+
+    glUniformMatrix2fv(location, count, transpose, value) {
+        ctx.uniformMatrix2fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 4);
+    },
+
+    glUniformMatrix3fv(location, count, transpose, value) {
+        ctx.uniformMatrix3fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 9);
+    },
+
+    glUniformMatrix4fv(location, count, transpose, value) {
+        ctx.uniformMatrix4fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 16);
+    },
+
+    glUseProgram(program) {
+        ctx.useProgram(programs.get(program));
+    },
+
+    glValidateProgram(program) {
+        ctx.validateProgram(programs.get(program));
+    },
+
+    glVertexAttrib1f(index, x) {
+        ctx.vertexAttrib1f(index, x);
+    },
+
+    glVertexAttrib1fv(index, v) {
+        ctx.vertexAttrib1fv(index, heapF32().subarray(v >> 2, (v >> 2) + 1));
+    },
+
+    glVertexAttrib2f(index, x, y) {
+        ctx.vertexAttrib2f(index, x, y);
+    },
+
+    glVertexAttrib2fv(index, v) {
+        ctx.vertexAttrib2fv(index, heapF32().subarray(v >> 2, (v >> 2) + 2));
+    },
+
+    glVertexAttrib3f(index, x, y, z) {
+        ctx.vertexAttrib3f(index, x, y, z);
+    },
+
+    glVertexAttrib3fv(index, v) {
+        ctx.vertexAttrib3fv(index, heapF32().subarray(v >> 2, (v >> 2) + 3));
+    },
+
+    glVertexAttrib4f(index, x, y, z, w) {
+        ctx.vertexAttrib4f(index, x, y, z, w);
+    },
+
+    glVertexAttrib4fv(index, v) {
+        ctx.vertexAttrib4fv(index, heapF32().subarray(v >> 2, (v >> 2) + 4));
+    },
+
+    glVertexAttribPointer(index, size, type, normalized, stride, pointer) {
+        ctx.vertexAttribPointer(index, size, type, !!normalized, stride, pointer);
+    },
+
+    glVertexAttribIPointer(index, size, type, stride, pointer) {
+        ctx.vertexAttribIPointer(index, size, type, stride, pointer);
+    },
+
+    glViewport(x, y, width, height) {
+        ctx.viewport(x, y, width, height);
+    },
+
+    glReadBuffer(src) {
+        ctx.readBuffer(src);
+    },
+
+    glDrawRangeElements(mode, start, end, count, type, indices) {
+        ctx.drawRangeElements(mode, start, end, count, type, indices);
+    },
+
+    glTexImage3D(target, level, internalformat, width, height, depth, border, format, type, pixels) {
+        if (unpackBuffer !== 0) {
+            ctx.texImage3D(target, level, internalformat, width, height, depth, border, format, type, pixels);
+        } else if (pixels === 0) {
+            ctx.texImage3D(target, level, internalformat, width, height, depth, border, format, type, null);
+        } else {
+            const Heap = heapForType(type);
+            ctx.texImage3D(target, level, internalformat, width, height, depth, border, format, type, new Heap(memory.buffer), pixels / Heap.BYTES_PER_ELEMENT);
+        }
+    },
+
+    glTexSubImage3D(target, level, xoffset, yoffset, zoffset, width, height, depth, format, type, pixels) {
+        if (unpackBuffer !== 0) {
+            ctx.texSubImage3D(target, level, xoffset, yoffset, zoffset, width, height, depth, format, type, pixels);
+        } else {
+            const Heap = heapForType(type);
+            ctx.texSubImage3D(target, level, xoffset, yoffset, zoffset, width, height, depth, format, type, new Heap(memory.buffer), pixels / Heap.BYTES_PER_ELEMENT);
+        }
+    },
+
+    glCopyTexSubImage3D(target, level, xoffset, yoffset, zoffset, x, y, width, height) {
+        ctx.copyTexSubImage3D(target, level, xoffset, yoffset, zoffset, x, y, width, height);
+    },
+
+    glCompressedTexImage3D(target, level, internalformat, width, height, depth, border, imageSize, data) {
+        if (unpackBuffer !== 0) {
+            ctx.compressedTexImage3D(target, level, internalformat, width, height, depth, border, imageSize, data);
+        } else {
+            ctx.compressedTexImage3D(target, level, internalformat, width, height, depth, border, heapU8(), data, imageSize);
+        }
+    },
+
+    glCompressedTexSubImage3D(target, level, xoffset, yoffset, zoffset, width, height, depth, format, imageSize, data) {
+        if (unpackBuffer !== 0) {
+            ctx.compressedTexSubImage3D(target, level, xoffset, yoffset, zoffset, width, height, depth, format, imageSize, data);
+        } else {
+            ctx.compressedTexSubImage3D(target, level, xoffset, yoffset, zoffset, width, height, depth, format, heapU8(), data, imageSize);
+        }
+    },
+
+    glGenQueries(n, ids) {
+        genObjects(queries, () => ctx.createQuery(), n, ids);
+    },
+
+    glDeleteQueries(n, ids) {
+        deleteObjects(queries, (query) => ctx.deleteQuery(query), n, ids);
+    },
+
+    glIsQuery(id) {
+        return ctx.isQuery(queries.get(id)) ? 1 : 0;
+    },
+
+    glBeginQuery(target, id) {
+        ctx.beginQuery(target, queries.get(id));
+    },
+
+    glEndQuery(target) {
+        ctx.endQuery(target);
+    },
+
+    glGetQueryiv(target, pname, params) {
+        writeValues(params, ctx.getQuery(target, pname));
+    },
+
+    glQueryCounter(id, target) {
+        unsupported("glQueryCounter");
+    },
+
+    glGetQueryObjectiv(id, pname, params) {
+        writeValues(params, ctx.getQueryParameter(queries.get(id), pname));
+    },
+
+    glGetQueryObjectui64v(id, pname, params) {
+        writeValues(params, ctx.getQueryParameter(queries.get(id), pname), "i64");
+    },
+
+    glUnmapBuffer(target) {
+        unsupported("glUnmapBuffer");
+        return 0;
+    },
+
+    glGetBufferPointerv(target, pname, params) {
+        // Buffers can never be mapped
+        writeValues(params, 0, "u32");
+    },
+
+    glDrawBuffers(n, bufs) {
+        ctx.drawBuffers(Array.from(heapU32().subarray(bufs >> 2, (bufs >> 2) + n)));
+    },
+
+    glUniformMatrix2x3fv(location, count, transpose, value) {
+        ctx.uniformMatrix2x3fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 6);
+    },
+
+    glUniformMatrix3x2fv(location, count, transpose, value) {
+        ctx.uniformMatrix3x2fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 6);
+    },
+
+    glUniformMatrix2x4fv(location, count, transpose, value) {
+        ctx.uniformMatrix2x4fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 8);
+    },
+
+    glUniformMatrix4x2fv(location, count, transpose, value) {
+        ctx.uniformMatrix4x2fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 8);
+    },
+
+    glUniformMatrix3x4fv(location, count, transpose, value) {
+        ctx.uniformMatrix3x4fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 12);
+    },
+
+    glUniformMatrix4x3fv(location, count, transpose, value) {
+        ctx.uniformMatrix4x3fv(uniforms.get(location), !!transpose, heapF32(), value >> 2, count * 12);
+    },
+
+    glBlitFramebuffer(srcX0, srcY0, srcX1, srcY1, dstX0, dstY0, dstX1, dstY1, mask, filter) {
+        ctx.blitFramebuffer(srcX0, srcY0, srcX1, srcY1, dstX0, dstY0, dstX1, dstY1, mask, filter);
+    },
+
+    glRenderbufferStorageMultisample(target, samples, internalformat, width, height) {
+        ctx.renderbufferStorageMultisample(target, samples, internalformat, width, height);
+    },
+
+    glFramebufferTextureLayer(target, attachment, texture, level, layer) {
+        ctx.framebufferTextureLayer(target, attachment, textures.get(texture), level, layer);
+    },
+
+    glMapBufferRange(target, offset, length, access) {
+        unsupported("glMapBufferRange");
+        return 0;
+    },
+
+    glFlushMappedBufferRange(target, offset, length) {
+        unsupported("glFlushMappedBufferRange");
+    },
+
+    glBindVertexArray(array) {
+        ctx.bindVertexArray(vertexArrays.get(array));
+    },
+
+    glDeleteVertexArrays(n, arrays) {
+        deleteObjects(vertexArrays, (array) => ctx.deleteVertexArray(array), n, arrays);
+    },
+
+    glGenVertexArrays(n, arrays) {
+        genObjects(vertexArrays, () => ctx.createVertexArray(), n, arrays);
+    },
+
+    glIsVertexArray(array) {
+        return ctx.isVertexArray(vertexArrays.get(array)) ? 1 : 0;
+    },
+
+    glGetIntegeri_v(target, index, data) {
+        writeValues(data, ctx.getIndexedParameter(target, index));
+    },
+
+    glBeginTransformFeedback(primitiveMode) {
+        ctx.beginTransformFeedback(primitiveMode);
+    },
+
+    glEndTransformFeedback() {
+        ctx.endTransformFeedback();
+    },
+
+    glBindBufferRange(target, index, buffer, offset, size) {
+        ctx.bindBufferRange(target, index, buffers.get(buffer), offset, size);
+    },
+
+    glBindBufferBase(target, index, buffer) {
+        ctx.bindBufferBase(target, index, buffers.get(buffer));
+    },
+
+    glTransformFeedbackVaryings(program, count, varyings, bufferMode) {
+        ctx.transformFeedbackVaryings(programs.get(program), readCStrings(count, varyings, 0), bufferMode);
+    },
+
+    glGetTransformFeedbackVarying(program, index, bufSize, length, size, type, name) {
+        const info = ctx.getTransformFeedbackVarying(programs.get(program), index);
+        writeActiveInfo(info, bufSize, length, size, type, name);
+    },
+
+    glGetVertexAttribIiv(index, pname, params) {
+        writeValues(params, ctx.getVertexAttrib(index, pname));
+    },
+
+    glGetVertexAttribIuiv(index, pname, params) {
+        writeValues(params, ctx.getVertexAttrib(index, pname), "u32");
+    },
+
+    glVertexAttribI4i(index, x, y, z, w) {
+        ctx.vertexAttribI4i(index, x, y, z, w);
+    },
+
+    glVertexAttribI4ui(index, x, y, z, w) {
+        ctx.vertexAttribI4ui(index, x, y, z, w);
+    },
+
+    glVertexAttribI4iv(index, v) {
+        ctx.vertexAttribI4iv(index, heapI32().subarray(v >> 2, (v >> 2) + 4));
+    },
+
+    glVertexAttribI4uiv(index, v) {
+        ctx.vertexAttribI4uiv(index, heapU32().subarray(v >> 2, (v >> 2) + 4));
+    },
+
+    glGetUniformuiv(program, location, params) {
+        writeValues(params, ctx.getUniform(programs.get(program), uniforms.get(location)), "u32");
+    },
+
+    glGetFragDataLocation(program, name) {
+        return ctx.getFragDataLocation(programs.get(program), readCString(name));
+    },
+
+    glUniform1ui(location, v0) {
+        ctx.uniform1ui(uniforms.get(location), v0);
+    },
+
+    glUniform2ui(location, v0, v1) {
+        ctx.uniform2ui(uniforms.get(location), v0, v1);
+    },
+
+    glUniform3ui(location, v0, v1, v2) {
+        ctx.uniform3ui(uniforms.get(location), v0, v1, v2);
+    },
+
+    glUniform4ui(location, v0, v1, v2, v3) {
+        ctx.uniform4ui(uniforms.get(location), v0, v1, v2, v3);
+    },
+
+    glUniform1uiv(location, count, value) {
+        ctx.uniform1uiv(uniforms.get(location), heapU32(), value >> 2, count);
+    },
+
+    glUniform2uiv(location, count, value) {
+        ctx.uniform2uiv(uniforms.get(location), heapU32(), value >> 2, count * 2);
+    },
+
+    glUniform3uiv(location, count, value) {
+        ctx.uniform3uiv(uniforms.get(location), heapU32(), value >> 2, count * 3);
+    },
+
+    glUniform4uiv(location, count, value) {
+        ctx.uniform4uiv(uniforms.get(location), heapU32(), value >> 2, count * 4);
+    },
+
+    glClearBufferiv(buffer, drawbuffer, value) {
+        ctx.clearBufferiv(buffer, drawbuffer, heapI32(), value >> 2);
+    },
+
+    glClearBufferuiv(buffer, drawbuffer, value) {
+        ctx.clearBufferuiv(buffer, drawbuffer, heapU32(), value >> 2);
+    },
+
+    glClearBufferfv(buffer, drawbuffer, value) {
+        ctx.clearBufferfv(buffer, drawbuffer, heapF32(), value >> 2);
+    },
+
+    glClearBufferfi(buffer, drawbuffer, depth, stencil) {
+        ctx.clearBufferfi(buffer, drawbuffer, depth, stencil);
+    },
+
+    glGetStringi(name, index) {
+        const extension = (ctx.getSupportedExtensions() ?? [])[index];
+        if (name !== GL_EXTENSIONS || extension === undefined) {
+            return 0;
+        }
+
+        return allocCString(`${name}:${index}`, extension);
+    },
+
+    glCopyBufferSubData(readTarget, writeTarget, readOffset, writeOffset, size) {
+        ctx.copyBufferSubData(readTarget, writeTarget, readOffset, writeOffset, size);
+    },
+
+    glGetUniformIndices(program, uniformCount, uniformNames, uniformIndices) {
+        const names = readCStrings(uniformCount, uniformNames, 0);
+        writeValues(uniformIndices, ctx.getUniformIndices(programs.get(program), names), "u32");
+    },
+
+    glGetActiveUniformsiv(program, uniformCount, uniformIndices, pname, params) {
+        const indices = Array.from(heapU32().subarray(uniformIndices >> 2, (uniformIndices >> 2) + uniformCount));
+        writeValues(params, ctx.getActiveUniforms(programs.get(program), indices, pname));
+    },
+
+    glGetUniformBlockIndex(program, uniformBlockName) {
+        return ctx.getUniformBlockIndex(programs.get(program), readCString(uniformBlockName));
+    },
+
+    glGetActiveUniformBlockiv(program, uniformBlockIndex, pname, params) {
+        writeValues(params, ctx.getActiveUniformBlockParameter(programs.get(program), uniformBlockIndex, pname));
+    },
+
+    glGetActiveUniformBlockName(program, uniformBlockIndex, bufSize, length, uniformBlockName) {
+        const name = ctx.getActiveUniformBlockName(programs.get(program), uniformBlockIndex);
+        writeCString(name, bufSize, length, uniformBlockName);
+    },
+
+    glUniformBlockBinding(program, uniformBlockIndex, uniformBlockBinding) {
+        ctx.uniformBlockBinding(programs.get(program), uniformBlockIndex, uniformBlockBinding);
+    },
+
+    glDrawArraysInstanced(mode, first, count, instancecount) {
+        ctx.drawArraysInstanced(mode, first, count, instancecount);
+    },
+
+    glDrawElementsInstanced(mode, count, type, indices, instancecount) {
+        ctx.drawElementsInstanced(mode, count, type, indices, instancecount);
+    },
+
+    glFenceSync(condition, flags) {
+        return syncs.add(ctx.fenceSync(condition, flags));
+    },
+
+    glIsSync(sync) {
+        return ctx.isSync(syncs.get(sync)) ? 1 : 0;
+    },
+
+    glDeleteSync(sync) {
+        const object = syncs.remove(sync);
+        if (object !== null) {
+            ctx.deleteSync(object);
+        }
+    },
+
+    glClientWaitSync(sync, flags, timeout) {
+        // WebGL caps the timeout (usually to 0), since blocking the main thread is a bad idea
+        const max = ctx.getParameter(ctx.MAX_CLIENT_WAIT_TIMEOUT_WEBGL);
+        return ctx.clientWaitSync(syncs.get(sync), flags, Math.min(Number(timeout), max));
+    },
+
+    glWaitSync(sync, flags, timeout) {
+        ctx.waitSync(syncs.get(sync), flags, ctx.TIMEOUT_IGNORED);
+    },
+
+    glGetInteger64v(pname, data) {
+        writeValues(data, ctx.getParameter(pname), "i64");
+    },
+
+    glGetSynciv(sync, pname, bufSize, length, values) {
+        if (bufSize > 0) {
+            writeValues(values, ctx.getSyncParameter(syncs.get(sync), pname));
+        }
+
+        writeValues(length, bufSize > 0 ? 1 : 0);
+    },
+
+    glGetInteger64i_v(target, index, data) {
+        writeValues(data, ctx.getIndexedParameter(target, index), "i64");
+    },
+
+    glGetBufferParameteri64v(target, pname, params) {
+        writeValues(params, ctx.getBufferParameter(target, pname), "i64");
+    },
+
+    glGenSamplers(count, ptr) {
+        genObjects(samplers, () => ctx.createSampler(), count, ptr);
+    },
+
+    glDeleteSamplers(count, ptr) {
+        deleteObjects(samplers, (sampler) => ctx.deleteSampler(sampler), count, ptr);
+    },
+
+    glIsSampler(sampler) {
+        return ctx.isSampler(samplers.get(sampler)) ? 1 : 0;
+    },
+
+    glBindSampler(unit, sampler) {
+        ctx.bindSampler(unit, samplers.get(sampler));
+    },
+
+    glSamplerParameteri(sampler, pname, param) {
+        ctx.samplerParameteri(samplers.get(sampler), pname, param);
+    },
+
+    glSamplerParameteriv(sampler, pname, param) {
+        ctx.samplerParameteri(samplers.get(sampler), pname, heapI32()[param >> 2]);
+    },
+
+    glSamplerParameterf(sampler, pname, param) {
+        ctx.samplerParameterf(samplers.get(sampler), pname, param);
+    },
+
+    glSamplerParameterfv(sampler, pname, param) {
+        ctx.samplerParameterf(samplers.get(sampler), pname, heapF32()[param >> 2]);
+    },
+
+    glGetSamplerParameteriv(sampler, pname, params) {
+        writeValues(params, ctx.getSamplerParameter(samplers.get(sampler), pname));
+    },
+
+    glGetSamplerParameterfv(sampler, pname, params) {
+        writeValues(params, ctx.getSamplerParameter(samplers.get(sampler), pname), "f32");
+    },
+
+    glVertexAttribDivisor(index, divisor) {
+        ctx.vertexAttribDivisor(index, divisor);
+    },
+
+    glBindTransformFeedback(target, id) {
+        ctx.bindTransformFeedback(target, transformFeedbacks.get(id));
+    },
+
+    glDeleteTransformFeedbacks(n, ids) {
+        deleteObjects(transformFeedbacks, (feedback) => ctx.deleteTransformFeedback(feedback), n, ids);
+    },
+
+    glGenTransformFeedbacks(n, ids) {
+        genObjects(transformFeedbacks, () => ctx.createTransformFeedback(), n, ids);
+    },
+
+    glIsTransformFeedback(id) {
+        return ctx.isTransformFeedback(transformFeedbacks.get(id)) ? 1 : 0;
+    },
+
+    glPauseTransformFeedback() {
+        ctx.pauseTransformFeedback();
+    },
+
+    glResumeTransformFeedback() {
+        ctx.resumeTransformFeedback();
+    },
+
+    glGetProgramBinary(program, bufSize, length, binaryFormat, binary) {
+        unsupported("glGetProgramBinary");
+        writeValues(length, 0);
+    },
+
+    glProgramBinary(program, binaryFormat, binary, length) {
+        unsupported("glProgramBinary");
+    },
+
+    glProgramParameteri(program, pname, value) {
+        unsupported("glProgramParameteri");
+    },
+
+    glInvalidateFramebuffer(target, numAttachments, attachments) {
+        const list = Array.from(heapU32().subarray(attachments >> 2, (attachments >> 2) + numAttachments));
+        ctx.invalidateFramebuffer(target, list);
+    },
+
+    glInvalidateSubFramebuffer(target, numAttachments, attachments, x, y, width, height) {
+        const list = Array.from(heapU32().subarray(attachments >> 2, (attachments >> 2) + numAttachments));
+        ctx.invalidateSubFramebuffer(target, list, x, y, width, height);
+    },
+
+    glTexStorage2D(target, levels, internalformat, width, height) {
+        ctx.texStorage2D(target, levels, internalformat, width, height);
+    },
+
+    glTexStorage3D(target, levels, internalformat, width, height, depth) {
+        ctx.texStorage3D(target, levels, internalformat, width, height, depth);
+    },
+
+    glGetInternalformativ(target, internalformat, pname, bufSize, params) {
+        const values = Array.from(ctx.getInternalformatParameter(target, internalformat, pname) ?? []);
+        writeValues(params, values.slice(0, bufSize));
+    },
+};
@@ -0,0 +1,128 @@
+/**
+ * The imports declared in `src/js.rs`
+ */
+
+// @helper PAGE_SIZE
+const PAGE_SIZE = Math.pow(2, 16);
+
+// @helper encoders
+const encoder = new TextDecoder("utf-8");
+const stringEncoder = new TextEncoder();
+
+// @helper HOST_STRINGS
+/**
+ * The sources of `js_read_string`, matching the `HostString` enum in `src/js.rs`
+ */
+const HOST_STRINGS = [
+    null,
+    (key) => localStorage.getItem(key),
+    (key) => new URLSearchParams(location.search).get(key),
+];
+
+// @helper timers
+// Browser timer handles by timer id
+const timers = new Map();
+
+// @helper settle
+/**
+ * Settle the `JsFuture` with the id `id` once `promise` settles. Fulfilled values must be numbers
+ * (or nothing), and rejections resolve the future to `None`
+ */
+function settle(id, promise) {
+    promise.then(
+        (value) => instance.exports.__resolve(id, 1, value ?? 0),
+        (error) => {
+            console.error(error);
+            instance.exports.__resolve(id, 0, 0);
+        }
+    );
+}
+
+const js = {
+    js_request_pages(pages) {
+        // `grow` throws once the module's maximum is reached
+        try {
+            return memory.grow(pages);
+        } catch {
+            return -1;
+        }
+    },
+
+    js_allocated_pages() {
+        return memory.buffer.byteLength / PAGE_SIZE
+    },
+
+    js_println(start, length) {
+        const view = new Uint8Array(memory.buffer, start, length);
+
+        console.log(encoder.decode(view));
+    },
+
+    js_log(level, start, length) {
+        const view = new Uint8Array(memory.buffer, start, length);
+        const log = [console.error, console.warn, console.info, console.debug][level - 1];
+
+        log(encoder.decode(view));
+    },
+
+    js_println_number(num) {
+        console.log(num);
+    },
+
+    js_alert(start, length) {
+        const view = new Uint8Array(memory.buffer, start, length);
+        alert(encoder.decode(view));
+    },
+
+    js_panic(errPtr, errLen, filePtr, fileLen, line) {
+        let message = "";
+        if (errLen > 0) {
+            const errView = new Uint8Array(memory.buffer, errPtr, errLen);
+            message = encoder.decode(errView);
+        }
+
+        const fileView = new Uint8Array(memory.buffer, filePtr, fileLen);
+        const filePath = encoder.decode(fileView);
+
+        message = `Caught a panic in ${filePath} at line ${line} :\n${message}`;
+
+        throw new Error(message);
+    },
+
+    js_read_string(source, keyPtr, keyLen, dst, capacity) {
+        const key = encoder.decode(new Uint8Array(memory.buffer, keyPtr, keyLen));
+        const value = HOST_STRINGS[source](key);
+        if (value === null) {
+            return -1;
+        }
+
+        // Only write strings that fit whole, so that Rust can retry with a bigger buffer
+        const bytes = stringEncoder.encode(value);
+        if (bytes.length <= capacity) {
+            new Uint8Array(memory.buffer, dst, bytes.length).set(bytes);
+        }
+
+        return bytes.length;
+    },
+
+    js_set_timer(id, ms, repeat) {
+        const handle = repeat
+            ? setInterval(() => instance.exports.__timer(id), ms)
+            : setTimeout(() => {
+                timers.delete(id);
+                instance.exports.__timer(id);
+            }, ms);
+
+        timers.set(id, handle);
+    },
+
+    js_clear_timer(id) {
+        // Timeouts and intervals share their handles, so this clears both
+        clearTimeout(timers.get(id));
+        timers.delete(id);
+    },
+
+    js_sleep(promise, ms) {
+        settle(promise, new Promise((resolve) => setTimeout(resolve, ms)));
+    },
+};
@@ -0,0 +1,288 @@
+// Generated by `./glue` from `web/glue`. Don't edit `web/app.js` directly
+
+/* PRELUDE */
+
+function assert(expr, message = "Assertion error") {
+    if (!expr) {
+        throw new Error(message)
+    }
+}
+
+let instance;
+let memory;
+
+const canvas = document.createElement("canvas")
+document.body.appendChild(canvas);
+
+// Maps `KeyboardEvent.code` onto the `KeyCode` enum in `src/input.rs`
+const KEYS = {
+    ArrowUp: 0,
+    ArrowDown: 1,
+    ArrowLeft: 2,
+    ArrowRight: 3,
+    KeyW: 4,
+    KeyA: 5,
+    KeyS: 6,
+    KeyD: 7,
+    Space: 8,
+    Escape: 9,
+    Enter: 10,
+    NumpadEnter: 10,
+};
+
+const ctx = canvas.getContext("webgl2");
+if (ctx === null) {
+    alert("This page needs webgl2 support and thus can't be run");
+    throw new Error("Failed to obtain a webgl2 context");
+} 
+
+/**
+ * Convert the client coordinates of an event into canvas pixel space, since the canvas' CSS size
+ * doesn't have to match the size of its backing store.
+ */
+function canvasPoint(event) {
+    const rect = canvas.getBoundingClientRect();
+
+    return [
+        (event.clientX - rect.left) * (canvas.width / rect.width),
+        (event.clientY - rect.top) * (canvas.height / rect.height),
+    ];
+}
+
+// How many pixels a single wheel line/page should scroll
+const WHEEL_LINE = 16;
+
+// Must match `GamepadState` and `MAX_GAMEPADS` in `src/gamepad.rs`
+const MAX_GAMEPADS = 4;
+const GAMEPAD_SIZE = 24;
+const GAMEPAD_AXES = 4;
+
+let canvasScale = 0;
+
+/**
+ * Match the canvas' backing store to its CSS size and the device pixel ratio, and let the
+ * app know if anything changed
+ */
+function resizeCanvas() {
+    const scale = window.devicePixelRatio || 1;
+    const rect = canvas.getBoundingClientRect();
+
+    const width = Math.max(1, Math.round(rect.width * scale));
+    const height = Math.max(1, Math.round(rect.height * scale));
+
+    if (width === canvas.width && height === canvas.height && scale === canvasScale) {
+        return;
+    }
+
+    canvas.width = width;
+    canvas.height = height;
+    canvasScale = scale;
+
+    instance.exports.__resize(width, height, scale);
+}
+
+let gamepads = 0;
+let gamepadCount = 0;
+
+/**
+ * Write the state of every connected pad into the gamepad buffer in linear memory
+ */
+function pollGamepads() {
+    if (gamepadCount === 0) {
+        return;
+    }
+
+    const view = new DataView(memory.buffer);
+    for (const pad of navigator.getGamepads()) {
+        if (pad === null || pad.index >= MAX_GAMEPADS) {
+            continue;
+        }
+
+        let buttons = 0;
+        pad.buttons.forEach((button, index) => {
+            if (button.pressed && index < 32) {
+                buttons |= 1 << index;
+            }
+        });
+
+        const base = gamepads + pad.index * GAMEPAD_SIZE;
+        view.setUint32(base + 4, buttons, true);
+
+        for (let axis = 0; axis < GAMEPAD_AXES; axis++) {
+            view.setFloat32(base + 8 + axis * 4, pad.axes[axis] ?? 0, true);
+        }
+    }
+}
+
+/**
+ * Call a Rust callback registered with `register_callback` or `register_closure`. This is the
+ * entry point for import libraries that complete later, like DOM events or fetches
+ */
+function callback(id, arg = 0) {
+    instance.exports.__callback(id, arg);
+}
+
+const env = {
+    /* IMPORTS */
+};
+
+WebAssembly.instantiateStreaming(fetch("./web/app.wasm"), { env }).then(
+    (results) => {
+        instance = results.instance;
+        memory = instance.exports.memory;
+
+        // Start the main program
+        instance.exports.__main();
+
+        // Only exported with the `heap-stats` feature
+        if (instance.exports.__heap_report !== undefined) {
+            window.heapReport = () => instance.exports.__heap_report();
+        }
+
+        // The app gets its initial size before the first frame
+        resizeCanvas();
+        new ResizeObserver(resizeCanvas).observe(canvas);
+
+        // Zooming changes the pixel ratio without resizing the canvas' CSS box
+        window.addEventListener("resize", resizeCanvas);
+
+        window.addEventListener("keydown", (event) => {
+            const code = KEYS[event.code];
+            if (code === undefined) {
+                return;
+            }
+
+            // Arrows and space would otherwise scroll the page
+            event.preventDefault();
+            if (!event.repeat) {
+                instance.exports.__key_down(code);
+            }
+        });
+
+        window.addEventListener("keyup", (event) => {
+            const code = KEYS[event.code];
+            if (code !== undefined) {
+                event.preventDefault();
+                instance.exports.__key_up(code);
+            }
+        });
+
+        // Touches have their own path below
+        canvas.addEventListener("pointermove", (event) => {
+            if (event.pointerType === "touch") {
+                return;
+            }
+
+            const [x, y] = canvasPoint(event);
+            instance.exports.__pointer_move(x, y);
+        });
+
+        canvas.addEventListener("pointerdown", (event) => {
+            if (event.pointerType === "touch") {
+                return;
+            }
+
+            const [x, y] = canvasPoint(event);
+
+            // Keep receiving events even if the pointer leaves the canvas while pressed
+            canvas.setPointerCapture(event.pointerId);
+            instance.exports.__pointer_down(event.button, x, y);
+        });
+
+        canvas.addEventListener("pointerup", (event) => {
+            if (event.pointerType === "touch") {
+                return;
+            }
+
+            const [x, y] = canvasPoint(event);
+            instance.exports.__pointer_up(event.button, x, y);
+        });
+
+        canvas.addEventListener("wheel", (event) => {
+            event.preventDefault();
+
+            let scale = 1;
+            if (event.deltaMode === WheelEvent.DOM_DELTA_LINE) {
+                scale = WHEEL_LINE;
+            } else if (event.deltaMode === WheelEvent.DOM_DELTA_PAGE) {
+                scale = canvas.height;
+            }
+
+            instance.exports.__wheel(event.deltaX * scale, event.deltaY * scale);
+        }, { passive: false });
+
+        function listenTouch(name, handler) {
+            canvas.addEventListener(name, (event) => {
+                // Stop the page from scrolling or zooming
+                event.preventDefault();
+
+                for (const touch of event.changedTouches) {
+                    const [x, y] = canvasPoint(touch);
+                    handler(touch.identifier, x, y);
+                }
+            }, { passive: false });
+        }
+
+        listenTouch("touchstart", instance.exports.__touch_start);
+        listenTouch("touchmove", instance.exports.__touch_move);
+        listenTouch("touchend", instance.exports.__touch_end);
+        listenTouch("touchcancel", instance.exports.__touch_cancel);
+
+        gamepads = instance.exports.__gamepads();
+
+        window.addEventListener("gamepadconnected", (event) => {
+            gamepadCount++;
+            instance.exports.__gamepad_connected(event.gamepad.index);
+        });
+
+        window.addEventListener("gamepaddisconnected", (event) => {
+            gamepadCount--;
+            instance.exports.__gamepad_disconnected(event.gamepad.index);
+        });
+
+        // The right mouse button is a regular button for us
+        canvas.addEventListener("contextmenu", (event) => event.preventDefault());
+
+        let frame = null;
+        let suspended = false;
+
+        function draw(time) {
+            pollGamepads();
+            instance.exports.__draw(time);
+            frame = requestAnimationFrame(draw);
+        }
+
+        /**
+         * Suspend the app when the page is hidden or unfocused, and only stop drawing entirely
+         * when it's hidden (an unfocused page can still show a pause screen)
+         */
+        function updateLifecycle() {
+            const shouldSuspend = document.hidden || !document.hasFocus();
+
+            if (shouldSuspend !== suspended) {
+                suspended = shouldSuspend;
+
+                if (suspended) {
+                    instance.exports.__suspended();
+                } else {
+                    instance.exports.__resumed();
+                }
+            }
+
+            if (document.hidden && frame !== null) {
+                cancelAnimationFrame(frame);
+                frame = null;
+            } else if (!document.hidden && frame === null) {
+                frame = requestAnimationFrame(draw);
+            }
+        }
+
+        document.addEventListener("visibilitychange", updateLifecycle);
+        window.addEventListener("blur", updateLifecycle);
+        window.addEventListener("focus", updateLifecycle);
+        window.addEventListener("pagehide", () => instance.exports.__before_unload());
+
+        // Starts the animation loop
+        updateLifecycle();
+    },
+);
\ No newline at end of file